@@ -0,0 +1,328 @@
+use crate::expression::{
+	Abs, Exponent, Expression, Fn, Fraction, Log, Product, Quotient, Sum, Trig, TrigFunction, E,
+};
+
+#[cfg(test)]
+mod tests {
+	use crate::expression::*;
+	use crate::*;
+
+	#[test]
+	fn powers() {
+		let exp = sum!(prod!(3, exp!("x", 4)), prod!(-2, "x"), 5);
+		assert_eq!(exp.differentiate("x").to_string(), "12x^3 - 2");
+		let exp = sqrt!("x");
+		assert_eq!(
+			exp.differentiate("x").to_string(),
+			"\\frac{1}{2}x^{- \\frac{1}{2}}"
+		);
+		let exp = quotient!(1, "x");
+		assert_eq!(exp.differentiate("x").to_string(), "- \\frac{1}{x^2}");
+		let exp = prod!("a", exp!("x", "n"));
+		assert_eq!(exp.differentiate("x").to_string(), "anx^{n - 1}");
+		// other variables are constants
+		let exp = sum!(prod!("a", exp!("x", 2)), "b");
+		assert_eq!(exp.differentiate("x").to_string(), "2ax");
+		assert_eq!(exp.differentiate("y").to_string(), "0");
+	}
+
+	#[test]
+	fn chain_rule() {
+		let exp = exp!(sum!(prod!(2, "x"), 1), 3);
+		assert_eq!(
+			exp.differentiate("x").to_string(),
+			"6\\left( 2x + 1 \\right)^2"
+		);
+		let exp = sqrt!(sum!(exp!("x", 2), 1));
+		assert_eq!(
+			exp.differentiate("x").to_string(),
+			"x\\left( x^2 + 1 \\right)^{- \\frac{1}{2}}"
+		);
+		let exp = exp_e!(prod!(3, "x"));
+		assert_eq!(exp.differentiate("x").to_string(), "3\\mathrm{e}^{3x}");
+		let exp = exp!(2, "x");
+		assert_eq!(exp.differentiate("x").to_string(), "2^x\\ln 2");
+	}
+
+	#[test]
+	fn product_and_quotient_rules() {
+		let exp = prod!(exp!("x", 2), sin!("x"));
+		assert_eq!(
+			exp.differentiate_unsimplified("x").to_string(),
+			"2x\\sin x + x^2\\cos x"
+		);
+		assert_eq!(exp.differentiate("x").to_string(), "2x\\sin x + x^2\\cos x");
+		let exp = prod!(exp!("x", 2), sum!("x", 1));
+		assert_eq!(
+			exp.differentiate_unsimplified("x").to_string(),
+			"2x\\left( x + 1 \\right) + x^2"
+		);
+		let exp = quotient!("x", sum!("x", 1));
+		assert_eq!(
+			exp.differentiate_unsimplified("x").to_string(),
+			"\\frac{x + 1 - x}{\\left( x + 1 \\right)^2}"
+		);
+		assert_eq!(
+			exp.differentiate("x").to_string(),
+			"\\frac{1}{\\left( x + 1 \\right)^2}"
+		);
+	}
+
+	#[test]
+	fn functions() {
+		assert_eq!(
+			sin!(prod!(2, "x")).differentiate("x").to_string(),
+			"2\\cos \\left( 2x \\right)"
+		);
+		assert_eq!(cos!("x").differentiate("x").to_string(), "- \\sin x");
+		assert_eq!(tan!("x").differentiate("x").to_string(), "\\sec^2 x");
+		assert_eq!(ln!("x").differentiate("x").to_string(), "\\frac{1}{x}");
+		assert_eq!(
+			ln!(sum!(exp!("x", 2), 1)).differentiate("x").to_string(),
+			"\\frac{2x}{x^2 + 1}"
+		);
+		assert_eq!(
+			log!(2, "x").differentiate("x").to_string(),
+			"\\frac{1}{x\\ln 2}"
+		);
+		assert_eq!(
+			abs!("x").differentiate("x").to_string(),
+			"\\frac{x}{\\left| x \\right|}"
+		);
+		assert_eq!(sin!(30).differentiate("x").to_string(), "0");
+	}
+}
+
+impl Expression {
+	// the derivative with respect to var, simplified
+	// other variables are treated as constants
+	pub fn differentiate(&self, var: &str) -> Expression {
+		let mut exp = derivative(self, var);
+		exp.simplify();
+		exp
+	}
+
+	// the derivative with respect to var, keeping the form of the
+	// product and quotient rules at the top level for working
+	// u'v + uv' and \frac{vu' - uv'}{v^2}
+	pub fn differentiate_unsimplified(&self, var: &str) -> Expression {
+		derivative(self, var)
+	}
+}
+
+// multiplies without simplifying, moving numerals and nested
+// coefficients to the front
+fn multiply(coefficient: Fraction, factors: Vec<Expression>) -> Expression {
+	let mut product = Product {
+		coefficient,
+		factors: Vec::new(),
+	};
+	for factor in factors {
+		match factor {
+			Expression::Numeral(n) => product.coefficient = product.coefficient * n,
+			Expression::Product(p) => {
+				product.coefficient = product.coefficient * p.coefficient;
+				product.factors.extend(p.factors);
+			}
+			_ => product.factors.push(Box::new(factor)),
+		}
+	}
+	if product.coefficient.is_zero() {
+		return Expression::Numeral(0.into());
+	}
+	if product.factors.is_empty() {
+		return Expression::Numeral(product.coefficient);
+	}
+	if product.factors.len() == 1 && product.coefficient.is_one() {
+		return *product.factors.remove(0);
+	}
+	Expression::Product(product)
+}
+
+fn sum(terms: Vec<Expression>) -> Expression {
+	let terms: Vec<Box<Expression>> = terms
+		.into_iter()
+		.filter(|t| !matches!(t, Expression::Numeral(n) if n.is_zero()))
+		.map(Box::new)
+		.collect();
+	match terms.len() {
+		0 => Expression::Numeral(0.into()),
+		1 => *terms[0].clone(),
+		_ => Expression::Sum(Sum { terms }),
+	}
+}
+
+fn power(base: &Expression, exponent: Expression) -> Expression {
+	let mut exp = Expression::Exponent(Exponent {
+		base: Box::new(base.clone()),
+		exponent: Box::new(exponent),
+	});
+	exp.simplify();
+	exp
+}
+
+// n/d, with a negative numeral or coefficient in n taken out in front: - \frac{1}{x^2}
+fn quotient(numerator: Expression, denominator: Expression) -> Expression {
+	let (sign, numerator) = match numerator {
+		Expression::Numeral(n) if n.is_negative() => (-1, Expression::Numeral(n.negative())),
+		Expression::Product(p) if p.coefficient.is_negative() => (
+			-1,
+			Expression::Product(Product {
+				coefficient: p.coefficient.negative(),
+				factors: p.factors,
+			}),
+		),
+		numerator => (1, numerator),
+	};
+	let exp = Expression::Quotient(Quotient {
+		numerator: Box::new(numerator),
+		denominator: Box::new(denominator),
+	});
+	if sign == 1 {
+		return exp;
+	}
+	Expression::Product(Product {
+		coefficient: (-1).into(),
+		factors: vec![Box::new(exp)],
+	})
+}
+
+fn trig(function: TrigFunction, argument: &Expression) -> Expression {
+	Expression::Fn(Fn::Trig(Trig {
+		function,
+		argument: Box::new(argument.clone()),
+	}))
+}
+
+fn ln(argument: &Expression) -> Expression {
+	let mut exp = Expression::Fn(Fn::Log(Log {
+		base: Box::new(E.into()),
+		argument: Box::new(argument.clone()),
+	}));
+	exp.simplify();
+	exp
+}
+
+// u' f'(u), with u' simplified
+fn chain(outer: Expression, inner: &Expression, var: &str) -> Expression {
+	multiply(1.into(), vec![inner.differentiate(var), outer])
+}
+
+fn derivative(exp: &Expression, var: &str) -> Expression {
+	if !exp.contains_variable(var) {
+		return Expression::Numeral(0.into());
+	}
+	match exp {
+		Expression::Variable(_) => Expression::Numeral(1.into()),
+		Expression::Sum(s) => sum(s.terms.iter().map(|t| t.differentiate(var)).collect()),
+		Expression::Product(p) => {
+			let (constants, factors): (Vec<&Box<Expression>>, Vec<&Box<Expression>>) =
+				p.factors.iter().partition(|f| !f.contains_variable(var));
+			let constants: Vec<Expression> = constants.into_iter().map(|f| *f.clone()).collect();
+			// product rule: differentiate one factor at a time
+			let mut terms: Vec<Expression> = Vec::new();
+			for i in 0..factors.len() {
+				let mut term = constants.clone();
+				for (j, factor) in factors.iter().enumerate() {
+					if i == j {
+						term.push(factor.differentiate(var));
+					} else {
+						term.push(factor.as_ref().clone());
+					}
+				}
+				terms.push(multiply(p.coefficient, term));
+			}
+			sum(terms)
+		}
+		Expression::Quotient(q) => {
+			let u = q.numerator.as_ref();
+			let v = q.denominator.as_ref();
+			if !v.contains_variable(var) {
+				return quotient(u.differentiate(var), v.clone());
+			}
+			// quotient rule
+			let numerator = sum(vec![
+				multiply(1.into(), vec![v.clone(), u.differentiate(var)]),
+				multiply((-1).into(), vec![u.clone(), v.differentiate(var)]),
+			]);
+			quotient(
+				numerator,
+				Expression::Exponent(Exponent {
+					base: Box::new(v.clone()),
+					exponent: Box::new(2.into()),
+				}),
+			)
+		}
+		Expression::Exponent(e) => {
+			let base = e.base.as_ref();
+			let exponent = e.exponent.as_ref();
+			if !exponent.contains_variable(var) {
+				// power rule: n u^{n - 1} u'
+				let mut n_minus_one = sum(vec![exponent.clone(), Expression::Numeral((-1).into())]);
+				n_minus_one.simplify();
+				let outer = multiply(1.into(), vec![exponent.clone(), power(base, n_minus_one)]);
+				chain(outer, base, var)
+			} else if !base.contains_variable(var) {
+				// b^u \ln b u'
+				chain(
+					multiply(1.into(), vec![exp.clone(), ln(base)]),
+					exponent,
+					var,
+				)
+			} else {
+				// u^v = \mathrm{e}^{v \ln u}
+				let logarithm = multiply(1.into(), vec![exponent.clone(), ln(base)]);
+				chain(exp.clone(), &logarithm, var)
+			}
+		}
+		Expression::Fn(f) => match f {
+			Fn::Brackets(b) => derivative(&b.expression, var),
+			Fn::Trig(t) => {
+				let u = t.argument.as_ref();
+				let outer = match t.function {
+					TrigFunction::Sin => trig(TrigFunction::Cos, u),
+					TrigFunction::Cos => multiply((-1).into(), vec![trig(TrigFunction::Sin, u)]),
+					TrigFunction::Tan => power(&trig(TrigFunction::Sec, u), 2.into()),
+					TrigFunction::Sec => multiply(
+						1.into(),
+						vec![trig(TrigFunction::Sec, u), trig(TrigFunction::Tan, u)],
+					),
+					TrigFunction::Csc => multiply(
+						(-1).into(),
+						vec![trig(TrigFunction::Csc, u), trig(TrigFunction::Cot, u)],
+					),
+					TrigFunction::Cot => multiply(
+						(-1).into(),
+						vec![power(&trig(TrigFunction::Csc, u), 2.into())],
+					),
+				};
+				chain(outer, u, var)
+			}
+			Fn::Log(l) => {
+				// \frac{u'}{u \ln b}
+				let u = l.argument.as_ref();
+				let denominator = if l.is_natural() {
+					u.clone()
+				} else {
+					multiply(1.into(), vec![u.clone(), ln(&l.base)])
+				};
+				Expression::Quotient(Quotient {
+					numerator: Box::new(u.differentiate(var)),
+					denominator: Box::new(denominator),
+				})
+			}
+			Fn::Abs(a) => {
+				// \frac{u}{|u|} u'
+				let u = a.argument.as_ref();
+				let outer = Expression::Quotient(Quotient {
+					numerator: Box::new(u.clone()),
+					denominator: Box::new(Expression::Fn(Fn::Abs(Abs {
+						argument: Box::new(u.clone()),
+					}))),
+				});
+				chain(outer, u, var)
+			}
+		},
+		Expression::Numeral(_) => Expression::Numeral(0.into()),
+	}
+}
@@ -0,0 +1,349 @@
+use crate::calculus::approximate;
+use crate::equation::linear_coefficients;
+use crate::expression::{
+	Abs, Exponent, Expression, Fn, Fraction, Log, Product, Quotient, SubIn, Sum, E,
+};
+
+#[cfg(test)]
+mod tests {
+	use crate::expression::*;
+	use crate::*;
+
+	#[test]
+	fn powers() {
+		let exp = sum!(prod!(3, exp!("x", 2)), prod!(-4, "x"), 5);
+		assert_eq!(exp.integrate("x").unwrap().to_string(), "x^3 - 2x^2 + 5x");
+		let exp = sqrt!("x");
+		assert_eq!(
+			exp.integrate("x").unwrap().to_string(),
+			"\\frac{2}{3}x^{\\frac{3}{2}}"
+		);
+		let exp = quotient!(2, exp!("x", 3));
+		assert_eq!(exp.integrate("x").unwrap().to_string(), "- x^{- 2}");
+		let exp = quotient!(1, "x");
+		assert_eq!(
+			exp.integrate("x").unwrap().to_string(),
+			"\\ln \\left| x \\right|"
+		);
+		let exp = prod!("k", exp!("x", -1));
+		assert_eq!(
+			exp.integrate("x").unwrap().to_string(),
+			"k\\ln \\left| x \\right|"
+		);
+		let exp = prod!("x", sin!("x"));
+		assert!(exp.integrate("x").is_none());
+	}
+
+	#[test]
+	fn linear_substitution() {
+		let exp = exp!(sum!(prod!(2, "x"), 1), 3);
+		assert_eq!(
+			exp.integrate("x").unwrap().to_string(),
+			"\\frac{1}{8}\\left( 2x + 1 \\right)^4"
+		);
+		let exp = quotient!(6, exp!(sum!(prod!(3, "x"), -1), 2));
+		assert_eq!(
+			exp.integrate("x").unwrap().to_string(),
+			"- 2\\left( 3x - 1 \\right)^{- 1}"
+		);
+		let exp = quotient!(1, sum!(prod!(2, "x"), 5));
+		assert_eq!(
+			exp.integrate("x").unwrap().to_string(),
+			"\\frac{1}{2}\\ln \\left| 2x + 5 \\right|"
+		);
+		let exp = sqrt!(sum!(4, prod!(-1, "x")));
+		assert_eq!(
+			exp.integrate("x").unwrap().to_string(),
+			"- \\frac{2}{3}\\left( 4 - x \\right)^{\\frac{3}{2}}"
+		);
+	}
+
+	#[test]
+	fn definite_integral() {
+		let exp = sum!(prod!(3, exp!("x", 2)), 1);
+		let mut steps: Vec<String> = Vec::new();
+		let value = exp
+			.definite_integral_with_steps("x", &1.into(), &2.into(), &mut steps)
+			.unwrap();
+		assert_eq!(value.to_string(), "8");
+		assert_eq!(
+			steps,
+			vec![
+				"\\int_{1}^{2} \\left( 3x^2 + 1 \\right) \\, \\mathrm{d}x = \\left[ x^3 + x \\right]_{1}^{2}",
+				"= \\left( 10 \\right) - \\left( 2 \\right)",
+				"= 8",
+			]
+		);
+		let exp = quotient!(1, "x");
+		let value = exp.definite_integral("x", &1.into(), &E.into()).unwrap();
+		assert_eq!(value.to_string(), "1");
+		let exp = quotient!(1, sum!("x", 1));
+		let value = exp.definite_integral("x", &0.into(), &3.into()).unwrap();
+		assert_eq!(value.to_string(), "\\ln 4");
+		// integrands undefined somewhere between the limits
+		let exp = quotient!(1, exp!("x", 2));
+		assert!(exp
+			.definite_integral("x", &(-1).into(), &1.into())
+			.is_none());
+		let exp = quotient!(1, "x");
+		assert!(exp
+			.definite_integral("x", &(-1).into(), &2.into())
+			.is_none());
+		let exp = sqrt!("x");
+		assert!(exp
+			.definite_integral("x", &(-4).into(), &0.into())
+			.is_none());
+		let value = exp.definite_integral("x", &0.into(), &4.into()).unwrap();
+		assert_eq!(value.to_string(), "\\frac{16}{3}");
+	}
+}
+
+impl Expression {
+	// an antiderivative with respect to var, without the constant of integration
+	// handles sums of kx^n and k(ax + b)^n, with n = -1 giving \ln |ax + b|
+	// returns None for other integrands
+	pub fn integrate(&self, var: &str) -> Option<Expression> {
+		let mut exp = antiderivative(self, var)?;
+		exp.simplify();
+		Some(exp)
+	}
+
+	// the exact value of the integral from a to b
+	pub fn definite_integral(&self, var: &str, a: &Expression, b: &Expression) -> Option<Expression> {
+		self.definite_integral_with_steps(var, a, b, &mut Vec::new())
+	}
+
+	// as definite_integral, recording the working
+	// \left[ F(x) \right]_a^b, then F(b) - F(a), then the value
+	// returns None if the integrand is undefined anywhere between a and b
+	pub fn definite_integral_with_steps(
+		&self,
+		var: &str,
+		a: &Expression,
+		b: &Expression,
+		steps: &mut Vec<String>,
+	) -> Option<Expression> {
+		let integral = self.integrate(var)?;
+		if !is_defined_between(self, var, a, b)? {
+			return None;
+		}
+		let upper = integral.sub_in(var, b);
+		let lower = integral.sub_in(var, a);
+		let integrand = match self {
+			Expression::Sum(s) if s.terms.len() > 1 => format!("\\left( {} \\right)", self),
+			_ => self.to_string(),
+		};
+		steps.push(format!(
+			"\\int_{{{}}}^{{{}}} {} \\, \\mathrm{{d}}{} = \\left[ {} \\right]_{{{}}}^{{{}}}",
+			a, b, integrand, var, integral, a, b
+		));
+		steps.push(format!(
+			"= \\left( {} \\right) - \\left( {} \\right)",
+			upper, lower
+		));
+		let mut value = Expression::Sum(Sum {
+			terms: vec![
+				Box::new(upper),
+				Box::new(Expression::Product(Product {
+					coefficient: (-1).into(),
+					factors: vec![Box::new(lower)],
+				})),
+			],
+		});
+		value.expand_and_simplify();
+		steps.push(format!("= {}", value));
+		Some(value)
+	}
+}
+
+// whether every power (ax + b)^n in the integrand is defined from a to b:
+// ax + b is nonzero for negative n and nonnegative for even roots
+// returns None if a bound or a base cannot be evaluated
+fn is_defined_between(exp: &Expression, var: &str, a: &Expression, b: &Expression) -> Option<bool> {
+	let (a, b) = (bound_value(a)?, bound_value(b)?);
+	let mut powers: Vec<(Expression, Fraction)> = Vec::new();
+	collect_powers(exp, var, 1.into(), &mut powers);
+	for (base, n) in powers {
+		if !n.is_negative() && n.denominator % 2 == 1 {
+			continue;
+		}
+		let (m, c) = linear_parts(&base, var)?;
+		let (m, c) = (
+			approximate(&Expression::Numeral(m))?,
+			approximate(&Expression::Numeral(c))?,
+		);
+		let (at_a, at_b) = (m * a + c, m * b + c);
+		// a linear base has a root between a and b if it changes sign
+		if n.is_negative() && at_a * at_b <= 0.0 {
+			return Some(false);
+		}
+		if n.denominator % 2 == 0 && (at_a < 0.0 || at_b < 0.0) {
+			return Some(false);
+		}
+	}
+	Some(true)
+}
+
+fn bound_value(bound: &Expression) -> Option<f64> {
+	match bound {
+		Expression::Variable(v) if v == E => Some(std::f64::consts::E),
+		Expression::Variable(v) if v == "\\pi" => Some(std::f64::consts::PI),
+		_ => approximate(bound),
+	}
+}
+
+// (base, n) for each power of an expression containing var, with 1/f as f^{-1}
+fn collect_powers(
+	exp: &Expression,
+	var: &str,
+	sign: Fraction,
+	powers: &mut Vec<(Expression, Fraction)>,
+) {
+	if !exp.contains_variable(var) {
+		return;
+	}
+	match exp {
+		Expression::Sum(s) => {
+			if sign.is_negative() {
+				powers.push((exp.clone(), sign));
+			}
+			for t in s.terms.iter() {
+				collect_powers(t, var, 1.into(), powers);
+			}
+		}
+		Expression::Product(p) => {
+			for f in p.factors.iter() {
+				collect_powers(f, var, sign, powers);
+			}
+		}
+		Expression::Quotient(q) => {
+			collect_powers(&q.numerator, var, sign, powers);
+			collect_powers(&q.denominator, var, sign.negative(), powers);
+		}
+		Expression::Exponent(e) => {
+			if let Expression::Numeral(n) = e.exponent.as_ref() {
+				powers.push((e.base.as_ref().clone(), *n * sign));
+			}
+			collect_powers(&e.base, var, 1.into(), powers);
+		}
+		_ => powers.push((exp.clone(), sign)),
+	}
+}
+
+// (a, b) for ax + b, where a is nonzero
+fn linear_parts(exp: &Expression, var: &str) -> Option<(Fraction, Fraction)> {
+	let mut exp = exp.clone();
+	exp.expand_and_simplify();
+	let (coefficients, constant) = linear_coefficients(&exp)?;
+	if coefficients.keys().any(|v| v != var) {
+		return None;
+	}
+	Some((*coefficients.get(var)?, constant))
+}
+
+// \int k(ax + b)^n \, \mathrm{d}x
+fn integrate_power(base: &Expression, n: Fraction, var: &str) -> Option<Expression> {
+	let (a, _) = linear_parts(base, var)?;
+	if n == (-1).into() {
+		// \frac{1}{a} \ln |ax + b|
+		let logarithm = Expression::Fn(Fn::Log(Log {
+			base: Box::new(E.into()),
+			argument: Box::new(Expression::Fn(Fn::Abs(Abs {
+				argument: Box::new(base.clone()),
+			}))),
+		}));
+		return Some(Expression::Product(Product {
+			coefficient: a.reciprocal(),
+			factors: vec![Box::new(logarithm)],
+		}));
+	}
+	let power = n + 1.into();
+	Some(Expression::Product(Product {
+		coefficient: (a * power).reciprocal(),
+		factors: vec![Box::new(Expression::Exponent(Exponent {
+			base: Box::new(base.clone()),
+			exponent: Box::new(Expression::Numeral(power)),
+		}))],
+	}))
+}
+
+fn antiderivative(exp: &Expression, var: &str) -> Option<Expression> {
+	if !exp.contains_variable(var) {
+		return Some(Expression::Product(Product {
+			coefficient: 1.into(),
+			factors: vec![Box::new(exp.clone()), Box::new(var.into())],
+		}));
+	}
+	match exp {
+		Expression::Variable(_) => integrate_power(exp, 1.into(), var),
+		Expression::Sum(s) => {
+			let mut terms: Vec<Box<Expression>> = Vec::new();
+			for t in s.terms.iter() {
+				terms.push(Box::new(antiderivative(t, var)?));
+			}
+			Some(Expression::Sum(Sum { terms }))
+		}
+		Expression::Product(p) => {
+			// constants multiply the integral of the single variable factor
+			let mut constants: Vec<Box<Expression>> = Vec::new();
+			let mut variable_factor: Option<&Expression> = None;
+			for factor in p.factors.iter() {
+				if !factor.contains_variable(var) {
+					constants.push(factor.clone());
+				} else if variable_factor.is_none() {
+					variable_factor = Some(factor);
+				} else {
+					return None;
+				}
+			}
+			constants.push(Box::new(antiderivative(variable_factor?, var)?));
+			Some(Expression::Product(Product {
+				coefficient: p.coefficient,
+				factors: constants,
+			}))
+		}
+		Expression::Quotient(q) => {
+			if !q.denominator.contains_variable(var) {
+				// \frac{f}{k}
+				return Some(Expression::Quotient(Quotient {
+					numerator: Box::new(antiderivative(&q.numerator, var)?),
+					denominator: q.denominator.clone(),
+				}));
+			}
+			if q.numerator.contains_variable(var) {
+				return None;
+			}
+			// \frac{k}{(ax + b)^n} = k(ax + b)^{-n}
+			let (base, n) = match q.denominator.as_ref() {
+				Expression::Exponent(e) => match e.exponent.as_ref() {
+					Expression::Numeral(n) => (e.base.as_ref(), *n),
+					_ => return None,
+				},
+				Expression::Product(p) if p.factors.len() == 1 => {
+					// \frac{k}{cf} = \frac{\frac{k}{c}}{f}
+					let quotient = Expression::Quotient(Quotient {
+						numerator: Box::new(Expression::Product(Product {
+							coefficient: p.coefficient.reciprocal(),
+							factors: vec![q.numerator.clone()],
+						})),
+						denominator: p.factors[0].clone(),
+					});
+					return antiderivative(&quotient, var);
+				}
+				denominator => (denominator, Fraction::from(1)),
+			};
+			Some(Expression::Product(Product {
+				coefficient: 1.into(),
+				factors: vec![
+					q.numerator.clone(),
+					Box::new(integrate_power(base, n.negative(), var)?),
+				],
+			}))
+		}
+		Expression::Exponent(e) => match e.exponent.as_ref() {
+			Expression::Numeral(n) => integrate_power(&e.base, *n, var),
+			_ => None,
+		},
+		_ => None,
+	}
+}
@@ -0,0 +1,11 @@
+pub mod differentiate;
+pub mod integrate;
+pub mod polynomial;
+pub mod series;
+pub mod stationary;
+pub mod tangent;
+pub use polynomial::{approximate, polynomial_coefficients, polynomial_roots};
+pub use stationary::{
+	stationary_points, stationary_points_with_steps, StationaryNature, StationaryPoint,
+};
+pub use tangent::{tangent_and_normal, tangent_and_normal_with_steps, TangentNormal};
@@ -0,0 +1,317 @@
+use crate::expression::{fraction_lcm, Exponent, Expression, Fraction, Product, Sum};
+
+#[cfg(test)]
+mod tests {
+	use crate::calculus::*;
+	use crate::expression::*;
+	use crate::*;
+
+	#[test]
+	fn coefficients() {
+		let exp = sum!(prod!(2, exp!("x", 3)), prod!(-1, "x"), 5);
+		let c = polynomial_coefficients(&exp, "x").unwrap();
+		assert_eq!(c, vec![5.into(), (-1).into(), 0.into(), 2.into()]);
+		let exp = prod!(sum!("x", 1), sum!("x", -1));
+		let c = polynomial_coefficients(&exp, "x").unwrap();
+		assert_eq!(c, vec![(-1).into(), 0.into(), 1.into()]);
+		assert!(polynomial_coefficients(&sqrt!("x"), "x").is_none());
+		assert!(polynomial_coefficients(&prod!("a", "x"), "x").is_none());
+	}
+
+	#[test]
+	fn roots() {
+		// 2x^3 - 3x^2 - 11x + 6 = (x - 3)(2x - 1)(x + 2)
+		let c: Vec<Fraction> = vec![6.into(), (-11).into(), (-3).into(), 2.into()];
+		let roots: Vec<String> = polynomial_roots(&c)
+			.unwrap()
+			.iter()
+			.map(|(r, m)| format!("{} {}", r, m))
+			.collect();
+		assert_eq!(roots, vec!["- 2 1", "\\frac{1}{2} 1", "3 1"]);
+		// x^3 (x - 1)^2
+		let c: Vec<Fraction> = vec![
+			0.into(),
+			0.into(),
+			0.into(),
+			1.into(),
+			(-2).into(),
+			1.into(),
+		];
+		let roots: Vec<String> = polynomial_roots(&c)
+			.unwrap()
+			.iter()
+			.map(|(r, m)| format!("{} {}", r, m))
+			.collect();
+		assert_eq!(roots, vec!["0 3", "1 2"]);
+		// x^2 - 2x - 1
+		let c: Vec<Fraction> = vec![(-1).into(), (-2).into(), 1.into()];
+		let roots: Vec<String> = polynomial_roots(&c)
+			.unwrap()
+			.iter()
+			.map(|(r, _)| r.to_string())
+			.collect();
+		assert_eq!(roots, vec!["1 - \\sqrt{2}", "1 + \\sqrt{2}"]);
+		// x^2 + 1
+		let c: Vec<Fraction> = vec![1.into(), 0.into(), 1.into()];
+		assert!(polynomial_roots(&c).unwrap().is_empty());
+		// (x - 1)(x^2 + 100000), where large candidates overflow i32
+		let c: Vec<Fraction> = vec![(-100000).into(), 100000.into(), (-1).into(), 1.into()];
+		let roots: Vec<String> = polynomial_roots(&c)
+			.unwrap()
+			.iter()
+			.map(|(r, _)| r.to_string())
+			.collect();
+		assert_eq!(roots, vec!["1"]);
+	}
+}
+
+// coefficients of a polynomial in var, indexed by power
+// returns None if the expression is not a polynomial with numeral coefficients
+pub fn polynomial_coefficients(exp: &Expression, var: &str) -> Option<Vec<Fraction>> {
+	let mut exp = exp.clone();
+	exp.expand_and_simplify();
+	let terms: Vec<Box<Expression>> = match exp {
+		Expression::Sum(s) => s.terms,
+		_ => vec![Box::new(exp)],
+	};
+	let mut coefficients: Vec<Fraction> = vec![0.into()];
+	for term in terms.iter() {
+		let (coefficient, power) = monomial(term, var)?;
+		if coefficients.len() <= power {
+			coefficients.resize(power + 1, 0.into());
+		}
+		coefficients[power] = coefficients[power] + coefficient;
+	}
+	while coefficients.len() > 1 && coefficients.last()?.is_zero() {
+		coefficients.pop();
+	}
+	Some(coefficients)
+}
+
+// (k, n) for kx^n
+fn monomial(exp: &Expression, var: &str) -> Option<(Fraction, usize)> {
+	match exp {
+		Expression::Numeral(n) => Some((*n, 0)),
+		Expression::Variable(v) if v == var => Some((1.into(), 1)),
+		Expression::Exponent(e) => match (e.base.as_ref(), e.exponent.as_ref()) {
+			(Expression::Variable(v), Expression::Numeral(n))
+				if v == var && n.is_integer() && n.is_positive() =>
+			{
+				Some((1.into(), n.numerator as usize))
+			}
+			_ => None,
+		},
+		Expression::Product(p) => match p.factors.len() {
+			0 => Some((p.coefficient, 0)),
+			1 => {
+				let (k, n) = monomial(&p.factors[0], var)?;
+				Some((k * p.coefficient, n))
+			}
+			_ => None,
+		},
+		_ => None,
+	}
+}
+
+// the polynomial with the given coefficients as an expression
+pub fn polynomial_expression(coefficients: &[Fraction], var: &str) -> Expression {
+	let mut terms: Vec<Box<Expression>> = Vec::new();
+	for (power, coefficient) in coefficients.iter().enumerate().rev() {
+		let factors: Vec<Box<Expression>> = match power {
+			0 => Vec::new(),
+			1 => vec![Box::new(var.into())],
+			_ => vec![Box::new(Expression::Exponent(Exponent {
+				base: Box::new(var.into()),
+				exponent: Box::new((power as i32).into()),
+			}))],
+		};
+		terms.push(Box::new(Expression::Product(Product {
+			coefficient: *coefficient,
+			factors,
+		})));
+	}
+	let mut exp = Expression::Sum(Sum { terms });
+	exp.simplify();
+	exp
+}
+
+// p(x) by Horner's method
+// returns None if the value, or a step on the way, is too large for a Fraction
+pub fn evaluate(coefficients: &[Fraction], x: Fraction) -> Option<Fraction> {
+	let mut total: (i128, i128) = (0, 1);
+	for c in coefficients.iter().rev() {
+		total = horner_step(total, x, *c)?;
+	}
+	to_fraction(total)
+}
+
+// divides by (x - r), assuming r is a root
+fn deflate(coefficients: &[Fraction], r: Fraction) -> Option<Vec<Fraction>> {
+	let n = coefficients.len() - 1;
+	let mut quotient: Vec<Fraction> = vec![0.into(); n];
+	let mut carry: (i128, i128) = (0, 1);
+	for i in (1..=n).rev() {
+		carry = horner_step(carry, r, coefficients[i])?;
+		quotient[i - 1] = to_fraction(carry)?;
+	}
+	Some(quotient)
+}
+
+// total * x + c, with total as (numerator, denominator) in lowest terms
+// the working is in i128 so that large rational root candidates do not overflow
+fn horner_step(total: (i128, i128), x: Fraction, c: Fraction) -> Option<(i128, i128)> {
+	let (n, d) = total;
+	let (xn, xd) = (x.numerator as i128, x.denominator as i128);
+	let (cn, cd) = (c.numerator as i128, c.denominator as i128);
+	let denominator = d.checked_mul(xd)?.checked_mul(cd)?;
+	let numerator = n
+		.checked_mul(xn)?
+		.checked_mul(cd)?
+		.checked_add(cn.checked_mul(d)?.checked_mul(xd)?)?;
+	let k = gcd_i128(numerator, denominator);
+	Some((numerator / k, denominator / k))
+}
+
+fn gcd_i128(mut a: i128, mut b: i128) -> i128 {
+	while b != 0 {
+		(a, b) = (b, a % b);
+	}
+	a.abs().max(1)
+}
+
+fn to_fraction((n, d): (i128, i128)) -> Option<Fraction> {
+	Some(Fraction::new(
+		i32::try_from(n).ok()?,
+		i32::try_from(d).ok()?,
+	))
+}
+
+// positive divisors of n, pairing each d up to \sqrt{n} with n / d
+fn divisors(n: i32) -> Vec<i32> {
+	let n = n.abs();
+	let mut small: Vec<i32> = Vec::new();
+	let mut large: Vec<i32> = Vec::new();
+	let mut d = 1;
+	while d <= n / d {
+		if n % d == 0 {
+			small.push(d);
+			if d != n / d {
+				large.push(n / d);
+			}
+		}
+		d += 1;
+	}
+	small.extend(large.into_iter().rev());
+	small
+}
+
+// counts a repeated root towards its multiplicity
+fn add_root(roots: &mut Vec<(Fraction, u32)>, r: Fraction) {
+	match roots.iter_mut().find(|(x, _)| *x == r) {
+		Some((_, m)) => *m += 1,
+		None => roots.push((r, 1)),
+	}
+}
+
+// exact real roots with their multiplicities, in increasing order
+// finds rational roots, then solves any remaining quadratic factor with surds
+// returns None if a factor of degree 3 or more remains
+pub fn polynomial_roots(coefficients: &[Fraction]) -> Option<Vec<(Expression, u32)>> {
+	let mut coefficients = coefficients.to_vec();
+	let mut roots: Vec<(Fraction, u32)> = Vec::new();
+	while coefficients.len() > 1 && coefficients[0].is_zero() {
+		coefficients.remove(0);
+		add_root(&mut roots, 0.into());
+	}
+	// rational root theorem on the integer polynomial
+	let denominator = coefficients.iter().fold(Fraction::from(1), |l, c| {
+		fraction_lcm(&l, &(c.denominator as i32).into())
+	});
+	let integers: Vec<i32> = coefficients
+		.iter()
+		.map(|c| (*c * denominator).numerator)
+		.collect();
+	let mut candidates: Vec<Fraction> = Vec::new();
+	if coefficients.len() > 2 {
+		for p in divisors(integers[0]) {
+			for q in divisors(*integers.last()?) {
+				candidates.push(Fraction::new(p, q));
+				candidates.push(Fraction::new(-p, q));
+			}
+		}
+	}
+	// candidates whose value overflows are too large to be roots
+	for r in candidates {
+		while coefficients.len() > 1 && evaluate(&coefficients, r).is_some_and(|v| v.is_zero()) {
+			coefficients = deflate(&coefficients, r)?;
+			add_root(&mut roots, r);
+		}
+	}
+	let mut exact: Vec<(Expression, u32)> = roots
+		.into_iter()
+		.map(|(r, m)| (Expression::Numeral(r), m))
+		.collect();
+	match coefficients.len() {
+		0 | 1 => (),
+		2 => exact.push((
+			Expression::Numeral((coefficients[0] / coefficients[1]).negative()),
+			1,
+		)),
+		3 => exact.extend(quadratic_roots(&coefficients).into_iter().map(|r| (r, 1))),
+		_ => return None,
+	}
+	exact.sort_by(|(a, _), (b, _)| {
+		approximate(a)
+			.unwrap_or(0.0)
+			.partial_cmp(&approximate(b).unwrap_or(0.0))
+			.unwrap_or(std::cmp::Ordering::Equal)
+	});
+	Some(exact)
+}
+
+// \frac{-b \pm \sqrt{b^2 - 4ac}}{2a} for a quadratic with no rational roots
+fn quadratic_roots(coefficients: &[Fraction]) -> Vec<Expression> {
+	let (c, b, a) = (coefficients[0], coefficients[1], coefficients[2]);
+	let discriminant = b * b - Fraction::from(4) * a * c;
+	if discriminant.is_negative() {
+		return Vec::new();
+	}
+	[Fraction::from(-1), Fraction::from(1)]
+		.iter()
+		.map(|sign| {
+			let mut root = Expression::Sum(Sum {
+				terms: vec![
+					Box::new(Expression::Numeral(b.negative() / (Fraction::from(2) * a))),
+					Box::new(Expression::Product(Product {
+						coefficient: *sign / (Fraction::from(2) * a),
+						factors: vec![Box::new(Expression::Exponent(Exponent {
+							base: Box::new(Expression::Numeral(discriminant)),
+							exponent: Box::new(Expression::Numeral(Fraction::new(1, 2))),
+						}))],
+					})),
+				],
+			});
+			root.simplify();
+			root
+		})
+		.collect()
+}
+
+// a decimal approximation, for comparing exact values
+pub fn approximate(exp: &Expression) -> Option<f64> {
+	match exp {
+		Expression::Numeral(n) => Some(n.numerator as f64 / n.denominator as f64),
+		Expression::Sum(s) => s.terms.iter().map(|t| approximate(t)).sum(),
+		Expression::Product(p) => {
+			let coefficient = approximate(&Expression::Numeral(p.coefficient))?;
+			p.factors
+				.iter()
+				.map(|f| approximate(f))
+				.product::<Option<f64>>()
+				.map(|x| x * coefficient)
+		}
+		Expression::Quotient(q) => Some(approximate(&q.numerator)? / approximate(&q.denominator)?),
+		Expression::Exponent(e) => Some(approximate(&e.base)?.powf(approximate(&e.exponent)?)),
+		_ => None,
+	}
+}
@@ -0,0 +1,267 @@
+use crate::equation::linear_coefficients;
+use crate::expression::{Exponent, Expression, Fn, Fraction, Product, Quotient, SubIn, Sum};
+use crate::inequality::{CompoundInequality, InequalitySign};
+
+#[cfg(test)]
+mod tests {
+	use crate::expression::*;
+	use crate::*;
+
+	#[test]
+	fn maclaurin() {
+		let exp = exp_e!("x");
+		assert_eq!(
+			exp.series("x", &0.into(), 3).unwrap().to_string(),
+			"1 + x + \\frac{1}{2}x^2 + \\frac{1}{6}x^3"
+		);
+		let exp = sin!("x");
+		assert_eq!(
+			exp.series("x", &0.into(), 5).unwrap().to_string(),
+			"x - \\frac{1}{6}x^3 + \\frac{1}{120}x^5"
+		);
+		let exp = ln!(sum!(1, prod!(2, "x")));
+		assert_eq!(
+			exp.series("x", &0.into(), 3).unwrap().to_string(),
+			"2x - 2x^2 + \\frac{8}{3}x^3"
+		);
+		assert_eq!(
+			exp.series_validity("x").unwrap().to_string(),
+			"- \\frac{1}{2} < x \\leq \\frac{1}{2}"
+		);
+		let exp = ln!(sum!(3, prod!(-1, "x")));
+		assert_eq!(
+			exp.series_validity("x").unwrap().to_string(),
+			"- 3 \\leq x < 3"
+		);
+		let exp = prod!(exp_e!("x"), cos!("x"));
+		assert_eq!(
+			exp.series("x", &0.into(), 3).unwrap().to_string(),
+			"1 + x - \\frac{1}{3}x^3"
+		);
+	}
+
+	#[test]
+	fn taylor() {
+		let exp = exp!("x", 3);
+		assert_eq!(
+			exp.series("x", &2.into(), 3).unwrap().to_string(),
+			"8 + 12\\left( x - 2 \\right) + 6\\left( x - 2 \\right)^2 + \\left( x - 2 \\right)^3"
+		);
+		let exp = ln!("x");
+		assert_eq!(
+			exp.series("x", &1.into(), 2).unwrap().to_string(),
+			"x - 1 - \\frac{1}{2}\\left( x - 1 \\right)^2"
+		);
+		// trig arguments are in degrees, so only 0 is supported
+		assert!(sin!("x").series("x", &1.into(), 2).is_none());
+		assert!(prod!("x", cos!("x")).series("x", &2.into(), 2).is_none());
+	}
+
+	#[test]
+	fn binomial() {
+		let exp = exp!(sum!(1, "x"), Fraction::new(1, 2));
+		assert_eq!(
+			exp.series("x", &0.into(), 3).unwrap().to_string(),
+			"1 + \\frac{1}{2}x - \\frac{1}{8}x^2 + \\frac{1}{16}x^3"
+		);
+		assert_eq!(exp.series_validity("x").unwrap().to_string(), "- 1 < x < 1");
+		let exp = quotient!(1, exp!(sum!(2, prod!(-1, "x")), 2));
+		assert_eq!(
+			exp.series("x", &0.into(), 2).unwrap().to_string(),
+			"\\frac{1}{4} + \\frac{1}{4}x + \\frac{3}{16}x^2"
+		);
+		assert_eq!(exp.series_validity("x").unwrap().to_string(), "- 2 < x < 2");
+		let exp = exp!(sum!(4, prod!(3, "x")), Fraction::new(-1, 2));
+		assert_eq!(
+			exp.series("x", &0.into(), 2).unwrap().to_string(),
+			"\\frac{1}{2} - \\frac{3}{16}x + \\frac{27}{256}x^2"
+		);
+		assert_eq!(
+			exp.series_validity("x").unwrap().to_string(),
+			"- \\frac{4}{3} < x < \\frac{4}{3}"
+		);
+		// positive integer powers are valid for all x
+		let exp = exp!(sum!(1, "x"), 3);
+		assert_eq!(
+			exp.series("x", &0.into(), 5).unwrap().to_string(),
+			"1 + 3x + 3x^2 + x^3"
+		);
+		assert!(exp.series_validity("x").is_none());
+	}
+}
+
+impl Expression {
+	// the Taylor polynomial about x = a up to the (x - a)^order term
+	// uses the generalised binomial theorem for (c + bx)^n about 0,
+	// and repeated differentiation otherwise
+	// numeral arguments of trig functions are in degrees, so those are only expanded about 0
+	// returns None for a trig function about any other point
+	pub fn series(&self, var: &str, about: &Fraction, order: u32) -> Option<Expression> {
+		let has_trig = self.fold(false, |found, e| {
+			found || matches!(e, Expression::Fn(Fn::Trig(_)))
+		});
+		if has_trig && !about.is_zero() {
+			return None;
+		}
+		if about.is_zero() {
+			if let Some((c, b, n)) = binomial_parts(self, var) {
+				return Some(binomial_series(var, c, b, n, order));
+			}
+		}
+		let point = Expression::Numeral(*about);
+		let mut derivative = self.clone();
+		let mut reciprocal_factorial = Fraction::from(1);
+		let mut terms: Vec<Box<Expression>> = Vec::new();
+		for k in 0..=order {
+			if k > 0 {
+				derivative = derivative.differentiate(var);
+				reciprocal_factorial = reciprocal_factorial / (k as i32).into();
+			}
+			let mut value = derivative.sub_in(var, &point);
+			value.expand_and_simplify();
+			if value.contains_variable(var) {
+				return None;
+			}
+			terms.push(Box::new(Expression::Product(Product {
+				coefficient: reciprocal_factorial,
+				factors: vec![Box::new(value), Box::new(power(var, about, k))],
+			})));
+		}
+		let mut exp = Expression::Sum(Sum { terms });
+		exp.simplify();
+		Some(exp)
+	}
+
+	// the range of x for which the series about 0 converges
+	// -|c/b| < x < |c/b| for the binomial series of (c + bx)^n
+	// and the same range with the endpoint where bx = c included for \ln (c + bx)
+	// returns None if the expansion is finite or not a binomial or logarithm series
+	pub fn series_validity(&self, var: &str) -> Option<CompoundInequality> {
+		if let Expression::Fn(Fn::Log(l)) = self {
+			let (c, b) = linear(&l.argument, var)?;
+			let (lower_sign, upper_sign) = if b.is_positive() {
+				(InequalitySign::LessThan, InequalitySign::LessThanOrEqual)
+			} else {
+				(InequalitySign::LessThanOrEqual, InequalitySign::LessThan)
+			};
+			return Some(symmetric_range(var, (c / b).abs(), lower_sign, upper_sign));
+		}
+		let (c, b, n) = binomial_parts(self, var)?;
+		if n.is_integer() && n.is_nonnegative() {
+			return None;
+		}
+		Some(symmetric_range(
+			var,
+			(c / b).abs(),
+			InequalitySign::LessThan,
+			InequalitySign::LessThan,
+		))
+	}
+}
+
+// -r < x < r, with the given signs
+fn symmetric_range(
+	var: &str,
+	r: Fraction,
+	lower_sign: InequalitySign,
+	upper_sign: InequalitySign,
+) -> CompoundInequality {
+	CompoundInequality {
+		lower: Box::new(Expression::Numeral(r.negative())),
+		lower_sign,
+		middle: Box::new(var.into()),
+		upper_sign,
+		upper: Box::new(Expression::Numeral(r)),
+	}
+}
+
+// (x - a)^k
+fn power(var: &str, about: &Fraction, k: u32) -> Expression {
+	let base = Expression::Sum(Sum {
+		terms: vec![
+			Box::new(var.into()),
+			Box::new(Expression::Numeral(about.negative())),
+		],
+	});
+	let mut exp = Expression::Exponent(Exponent {
+		base: Box::new(base),
+		exponent: Box::new(Expression::Numeral((k as i32).into())),
+	});
+	exp.simplify();
+	exp
+}
+
+// (c, b, n) for (c + bx)^n or \frac{k}{(c + bx)^m}, with c > 0 and b nonzero
+// a numerator k is absorbed into c and b only when it is 1
+fn binomial_parts(exp: &Expression, var: &str) -> Option<(Fraction, Fraction, Fraction)> {
+	let (base, n) = match exp {
+		Expression::Exponent(e) => match e.exponent.as_ref() {
+			Expression::Numeral(n) => (e.base.as_ref(), *n),
+			_ => return None,
+		},
+		Expression::Quotient(q) => {
+			let numerator: Fraction = q.numerator.as_ref().clone().try_into().ok()?;
+			if !numerator.is_one() {
+				return None;
+			}
+			let (c, b, n) = match q.denominator.as_ref() {
+				Expression::Exponent(_) => binomial_parts(&q.denominator, var)?,
+				denominator => {
+					let (c, b) = linear(denominator, var)?;
+					(c, b, 1.into())
+				}
+			};
+			return Some((c, b, n.negative()));
+		}
+		_ => return None,
+	};
+	let (c, b) = linear(base, var)?;
+	Some((c, b, n))
+}
+
+// (c, b) for c + bx with c > 0
+fn linear(exp: &Expression, var: &str) -> Option<(Fraction, Fraction)> {
+	let (coefficients, constant) = linear_coefficients(exp)?;
+	if coefficients.keys().any(|v| v != var) || !constant.is_positive() {
+		return None;
+	}
+	Some((constant, *coefficients.get(var)?))
+}
+
+// c^n (1 + \frac{b}{c}x)^n = c^n \sum \binom{n}{k} (\frac{b}{c}x)^k
+fn binomial_series(var: &str, c: Fraction, b: Fraction, n: Fraction, order: u32) -> Expression {
+	// c^{-n} is written \frac{1}{c^n} so that roots of c are taken exactly
+	let mut leading = Expression::Exponent(Exponent {
+		base: Box::new(Expression::Numeral(c)),
+		exponent: Box::new(Expression::Numeral(n.abs())),
+	});
+	if n.is_negative() {
+		leading = Expression::Quotient(Quotient {
+			numerator: Box::new(Expression::Numeral(1.into())),
+			denominator: Box::new(leading),
+		});
+	}
+	leading.simplify();
+	let ratio = b / c;
+	let mut coefficient = Fraction::from(1);
+	let mut terms: Vec<Box<Expression>> = Vec::new();
+	for k in 0..=order {
+		if k > 0 {
+			// \binom{n}{k} = \binom{n}{k - 1} \frac{n - k + 1}{k}
+			coefficient = coefficient * (n - (k as i32 - 1).into()) / (k as i32).into() * ratio;
+		}
+		if coefficient.is_zero() {
+			break;
+		}
+		terms.push(Box::new(Expression::Product(Product {
+			coefficient,
+			factors: vec![Box::new(power(var, &0.into(), k))],
+		})));
+	}
+	let mut exp = Expression::Product(Product {
+		coefficient: 1.into(),
+		factors: vec![Box::new(leading), Box::new(Expression::Sum(Sum { terms }))],
+	});
+	exp.expand_and_simplify();
+	exp
+}
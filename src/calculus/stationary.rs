@@ -0,0 +1,239 @@
+use crate::calculus::polynomial::{
+	approximate, evaluate, polynomial_coefficients, polynomial_roots,
+};
+use crate::expression::{Expression, Fraction, SubIn};
+use std::fmt;
+
+#[cfg(test)]
+mod tests {
+	use crate::calculus::*;
+	use crate::expression::*;
+	use crate::*;
+
+	#[test]
+	fn cubic() {
+		// y = x^3 - 3x + 2
+		let exp = sum!(exp!("x", 3), prod!(-3, "x"), 2);
+		let mut steps: Vec<String> = Vec::new();
+		let points = stationary_points_with_steps(&exp, "x", &mut steps).unwrap();
+		let points: Vec<String> = points
+			.iter()
+			.map(|p| format!("{} {}", p, p.nature))
+			.collect();
+		assert_eq!(
+			points,
+			vec![
+				"\\left( - 1, 4 \\right) maximum point",
+				"\\left( 1, 0 \\right) minimum point"
+			]
+		);
+		assert_eq!(
+			steps,
+			vec![
+				"\\frac{\\mathrm{d}y}{\\mathrm{d}x} = 3x^2 - 3",
+				"\\frac{\\mathrm{d}y}{\\mathrm{d}x} = 0 \\implies x = - 1 \\text{ or } x = 1",
+				"\\frac{\\mathrm{d}^2y}{\\mathrm{d}x^2} = 6x",
+				"\\text{When } x = - 1, y = 4 \\text{ and } \\frac{\\mathrm{d}^2y}{\\mathrm{d}x^2} = - 6",
+				"\\implies \\left( - 1, 4 \\right) \\text{ is a maximum point}",
+				"\\text{When } x = 1, y = 0 \\text{ and } \\frac{\\mathrm{d}^2y}{\\mathrm{d}x^2} = 6",
+				"\\implies \\left( 1, 0 \\right) \\text{ is a minimum point}",
+			]
+		);
+	}
+
+	#[test]
+	fn inflexion() {
+		// y = x^3
+		let exp = exp!("x", 3);
+		let mut steps: Vec<String> = Vec::new();
+		let points = stationary_points_with_steps(&exp, "x", &mut steps).unwrap();
+		assert_eq!(points.len(), 1);
+		assert_eq!(points[0].nature, StationaryNature::Inflexion);
+		assert_eq!(steps, vec![
+			"\\frac{\\mathrm{d}y}{\\mathrm{d}x} = 3x^2",
+			"\\frac{\\mathrm{d}y}{\\mathrm{d}x} = 0 \\implies x = 0",
+			"\\frac{\\mathrm{d}^2y}{\\mathrm{d}x^2} = 6x",
+			"\\text{When } x = 0, y = 0 \\text{ and } \\frac{\\mathrm{d}^2y}{\\mathrm{d}x^2} = 0",
+			"\\text{When } x = - \\frac{1}{2}, \\frac{\\mathrm{d}y}{\\mathrm{d}x} = \\frac{3}{4} > 0 \\text{ and when } x = \\frac{1}{2}, \\frac{\\mathrm{d}y}{\\mathrm{d}x} = \\frac{3}{4} > 0",
+			"\\implies \\left( 0, 0 \\right) \\text{ is a point of inflexion}",
+		]);
+		// y = x^4 has a minimum even though the second derivative is 0
+		let points = stationary_points(&exp!("x", 4), "x").unwrap();
+		assert_eq!(points[0].nature, StationaryNature::Minimum);
+		// y' = 5x^4 - 5000 has large root candidates and no exact roots
+		let exp = sum!(exp!("x", 5), prod!(-5000, "x"));
+		assert!(stationary_points(&exp, "x").is_none());
+	}
+
+	#[test]
+	fn surd_coordinates() {
+		// y = x^3 - 3x^2 - 3x, y' = 3(x^2 - 2x - 1)
+		let exp = sum!(exp!("x", 3), prod!(-3, exp!("x", 2)), prod!(-3, "x"));
+		let points: Vec<String> = stationary_points(&exp, "x")
+			.unwrap()
+			.iter()
+			.map(|p| format!("{} {}", p, p.nature))
+			.collect();
+		assert_eq!(
+			points,
+			vec![
+				"\\left( 1 - \\sqrt{2}, - 5 + 4\\sqrt{2} \\right) maximum point",
+				"\\left( 1 + \\sqrt{2}, - 5 - 4\\sqrt{2} \\right) minimum point",
+			]
+		);
+		// no stationary points
+		let exp = sum!(exp!("x", 3), "x");
+		assert!(stationary_points(&exp, "x").unwrap().is_empty());
+		assert!(stationary_points(&sqrt!("x"), "x").is_none());
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StationaryNature {
+	Maximum,
+	Minimum,
+	Inflexion,
+}
+
+impl fmt::Display for StationaryNature {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			StationaryNature::Maximum => write!(f, "maximum point"),
+			StationaryNature::Minimum => write!(f, "minimum point"),
+			StationaryNature::Inflexion => write!(f, "point of inflexion"),
+		}
+	}
+}
+
+#[derive(Debug, Clone)]
+pub struct StationaryPoint {
+	pub x: Expression,
+	pub y: Expression,
+	pub nature: StationaryNature,
+}
+
+impl fmt::Display for StationaryPoint {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "\\left( {}, {} \\right)", self.x, self.y)
+	}
+}
+
+// stationary points of a polynomial in var, in increasing order of x
+// returns None if the expression is not a polynomial or f'(x) = 0 cannot be solved exactly
+pub fn stationary_points(exp: &Expression, var: &str) -> Option<Vec<StationaryPoint>> {
+	stationary_points_with_steps(exp, var, &mut Vec::new())
+}
+
+// as stationary_points, recording the working
+// the nature is found with the second derivative test,
+// falling back to the first derivative test when the second derivative is 0
+pub fn stationary_points_with_steps(
+	exp: &Expression,
+	var: &str,
+	steps: &mut Vec<String>,
+) -> Option<Vec<StationaryPoint>> {
+	let first = exp.differentiate(var);
+	let second = first.differentiate(var);
+	let coefficients = polynomial_coefficients(&first, var)?;
+	if coefficients.iter().all(|c| c.is_zero()) {
+		return None;
+	}
+	let roots = polynomial_roots(&coefficients)?;
+	let dy = format!("\\frac{{\\mathrm{{d}}y}}{{\\mathrm{{d}}{}}}", var);
+	let d2y = format!("\\frac{{\\mathrm{{d}}^2y}}{{\\mathrm{{d}}{}^2}}", var);
+	steps.push(format!("{} = {}", dy, first));
+	if roots.is_empty() {
+		steps.push(format!("{} = 0 \\text{{ has no real solutions}}", dy));
+		return Some(Vec::new());
+	}
+	let solutions: Vec<String> = roots
+		.iter()
+		.map(|(r, _)| format!("{} = {}", var, r))
+		.collect();
+	steps.push(format!(
+		"{} = 0 \\implies {}",
+		dy,
+		solutions.join(" \\text{ or } ")
+	));
+	steps.push(format!("{} = {}", d2y, second));
+	let mut points: Vec<StationaryPoint> = Vec::new();
+	for (x, _) in roots.iter() {
+		let y = value_at(exp, var, x);
+		let curvature = value_at(&second, var, x);
+		steps.push(format!(
+			"\\text{{When }} {} = {}, y = {} \\text{{ and }} {} = {}",
+			var, x, y, d2y, curvature
+		));
+		let sign = approximate(&curvature)?;
+		let nature = if sign < 0.0 {
+			StationaryNature::Maximum
+		} else if sign > 0.0 {
+			StationaryNature::Minimum
+		} else {
+			// repeated roots of f' are rational, so the test points are exact
+			let x: Fraction = x.clone().try_into().ok()?;
+			let others: Vec<f64> = roots.iter().filter_map(|(r, _)| approximate(r)).collect();
+			first_derivative_test(&coefficients, var, x, &others, steps)?
+		};
+		let point = StationaryPoint {
+			x: x.clone(),
+			y,
+			nature,
+		};
+		steps.push(format!("\\implies {} \\text{{ is a {}}}", point, nature));
+		points.push(point);
+	}
+	Some(points)
+}
+
+fn value_at(exp: &Expression, var: &str, x: &Expression) -> Expression {
+	let mut value = exp.sub_in(var, x);
+	value.expand_and_simplify();
+	value
+}
+
+// compares the sign of f' on either side of x, between x and any neighbouring roots
+fn first_derivative_test(
+	coefficients: &[Fraction],
+	var: &str,
+	x: Fraction,
+	roots: &[f64],
+	steps: &mut Vec<String>,
+) -> Option<StationaryNature> {
+	let centre = x.numerator as f64 / x.denominator as f64;
+	let mut delta = Fraction::new(1, 2);
+	let width = |d: &Fraction| d.numerator as f64 / d.denominator as f64;
+	while roots
+		.iter()
+		.any(|r| *r != centre && (r - centre).abs() <= width(&delta))
+	{
+		delta = delta / 2.into();
+	}
+	let sign = |v: &Fraction| if v.is_positive() { ">" } else { "<" };
+	let (left, right) = (x - delta, x + delta);
+	let (left_value, right_value) = (
+		evaluate(coefficients, left)?,
+		evaluate(coefficients, right)?,
+	);
+	let dy = format!("\\frac{{\\mathrm{{d}}y}}{{\\mathrm{{d}}{}}}", var);
+	steps.push(format!(
+		"\\text{{When }} {} = {}, {} = {} {} 0 \\text{{ and when }} {} = {}, {} = {} {} 0",
+		var,
+		left,
+		dy,
+		left_value,
+		sign(&left_value),
+		var,
+		right,
+		dy,
+		right_value,
+		sign(&right_value)
+	));
+	Some(
+		match (left_value.is_positive(), right_value.is_positive()) {
+			(true, false) => StationaryNature::Maximum,
+			(false, true) => StationaryNature::Minimum,
+			_ => StationaryNature::Inflexion,
+		},
+	)
+}
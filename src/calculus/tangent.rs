@@ -0,0 +1,189 @@
+use crate::equation::Equation;
+use crate::expression::{Expression, Fraction, Product, SubIn, Sum};
+
+#[cfg(test)]
+mod tests {
+	use crate::calculus::*;
+	use crate::expression::*;
+	use crate::*;
+
+	#[test]
+	fn tangent_and_normal_lines() {
+		// y = x^2 at x = 2
+		let curve = exp!("x", 2);
+		let mut steps: Vec<String> = Vec::new();
+		let lines = tangent_and_normal_with_steps(&curve, "x", &2.into(), &mut steps).unwrap();
+		assert_eq!(lines.point, (2.into(), 4.into()));
+		assert_eq!(lines.gradient, 4.into());
+		assert_eq!(lines.tangent.to_string(), "y = 4x - 4");
+		assert_eq!(
+			lines.normal.to_string(),
+			"y = - \\frac{1}{4}x + \\frac{9}{2}"
+		);
+		assert_eq!(
+			steps,
+			vec![
+				"\\frac{\\mathrm{d}y}{\\mathrm{d}x} = 2x",
+				"\\text{When } x = 2, y = 4 \\text{ and } \\frac{\\mathrm{d}y}{\\mathrm{d}x} = 4",
+				"\\text{Tangent: } y - 4 = 4\\left( x - 2 \\right)",
+				"y = 4x - 4",
+				"\\text{Gradient of normal} = - \\frac{1}{4}",
+				"\\text{Normal: } y - 4 = - \\frac{1}{4}\\left( x - 2 \\right)",
+				"y = - \\frac{1}{4}x + \\frac{9}{2}",
+			]
+		);
+		// y = \frac{1}{x} at x = -1
+		let curve = quotient!(1, "x");
+		let lines = tangent_and_normal(&curve, "x", &(-1).into()).unwrap();
+		assert_eq!(lines.tangent.to_string(), "y = - x - 2");
+		assert_eq!(lines.normal.to_string(), "y = x");
+		// undefined at x = 0
+		assert!(tangent_and_normal(&curve, "x", &0.into()).is_none());
+		let curve = exp!("x", -2);
+		assert!(tangent_and_normal(&curve, "x", &0.into()).is_none());
+	}
+
+	#[test]
+	fn horizontal_tangent() {
+		// y = x^2 - 2x + 3 at x = 1
+		let curve = sum!(exp!("x", 2), prod!(-2, "x"), 3);
+		let lines = tangent_and_normal(&curve, "x", &1.into()).unwrap();
+		assert_eq!(lines.tangent.to_string(), "y = 2");
+		assert_eq!(lines.normal.to_string(), "x = 1");
+		// irrational gradients are not supported
+		assert!(tangent_and_normal(&sqrt!("x"), "x", &2.into()).is_none());
+	}
+}
+
+// the tangent and normal to a curve at a point
+// equations are in the form y = mx + c, or x = k for a vertical normal
+#[derive(Debug, Clone)]
+pub struct TangentNormal {
+	pub point: (Fraction, Fraction),
+	pub gradient: Fraction,
+	pub tangent: Equation,
+	pub normal: Equation,
+}
+
+// the tangent and normal to y = f(x) at x = x_1
+// returns None if the coordinates or gradient are not rational, or the curve is undefined at x_1
+pub fn tangent_and_normal(curve: &Expression, var: &str, x: &Fraction) -> Option<TangentNormal> {
+	tangent_and_normal_with_steps(curve, var, x, &mut Vec::new())
+}
+
+// as tangent_and_normal, recording the working with y - y_1 = m(x - x_1)
+pub fn tangent_and_normal_with_steps(
+	curve: &Expression,
+	var: &str,
+	x: &Fraction,
+	steps: &mut Vec<String>,
+) -> Option<TangentNormal> {
+	if !is_defined_at(curve, var, x) {
+		return None;
+	}
+	let derivative = curve.differentiate(var);
+	let mut y = curve.sub_in(var, &Expression::Numeral(*x));
+	y.expand_and_simplify();
+	let y: Fraction = y.try_into().ok()?;
+	let mut gradient = derivative.sub_in(var, &Expression::Numeral(*x));
+	gradient.expand_and_simplify();
+	let gradient: Fraction = gradient.try_into().ok()?;
+	let dy = format!("\\frac{{\\mathrm{{d}}y}}{{\\mathrm{{d}}{}}}", var);
+	steps.push(format!("{} = {}", dy, derivative));
+	steps.push(format!(
+		"\\text{{When }} {} = {}, y = {} \\text{{ and }} {} = {}",
+		var, x, y, dy, gradient
+	));
+	let tangent = line(var, *x, y, gradient);
+	steps.push(format!(
+		"\\text{{Tangent: }} {}",
+		point_gradient_form(var, *x, y, gradient)
+	));
+	steps.push(tangent.to_string());
+	let normal = if gradient.is_zero() {
+		steps.push("\\text{Normal: } \\text{vertical line}".to_string());
+		Equation {
+			lhs: Box::new(var.into()),
+			rhs: Box::new(Expression::Numeral(*x)),
+		}
+	} else {
+		let normal_gradient = gradient.reciprocal().negative();
+		steps.push(format!(
+			"\\text{{Gradient of normal}} = {}",
+			normal_gradient
+		));
+		steps.push(format!(
+			"\\text{{Normal: }} {}",
+			point_gradient_form(var, *x, y, normal_gradient)
+		));
+		line(var, *x, y, normal_gradient)
+	};
+	steps.push(normal.to_string());
+	Some(TangentNormal {
+		point: (*x, y),
+		gradient,
+		tangent,
+		normal,
+	})
+}
+
+// false if a denominator, or the base of a negative power, is zero at x
+// inner expressions are checked first as substituting into an outer one simplifies them
+fn is_defined_at(exp: &Expression, var: &str, x: &Fraction) -> bool {
+	let is_zero = |exp: &Expression| {
+		let mut value = exp.sub_in(var, &Expression::Numeral(*x));
+		value.expand_and_simplify();
+		matches!(value, Expression::Numeral(n) if n.is_zero())
+	};
+	match exp {
+		Expression::Sum(s) => s.terms.iter().all(|t| is_defined_at(t, var, x)),
+		Expression::Product(p) => p.factors.iter().all(|f| is_defined_at(f, var, x)),
+		Expression::Quotient(q) => {
+			is_defined_at(&q.numerator, var, x)
+				&& is_defined_at(&q.denominator, var, x)
+				&& !is_zero(&q.denominator)
+		}
+		Expression::Exponent(e) => {
+			let negative = matches!(e.exponent.as_ref(), Expression::Numeral(n) if n.is_negative());
+			is_defined_at(&e.base, var, x) && !(negative && is_zero(&e.base))
+		}
+		_ => true,
+	}
+}
+
+// y = mx + c through (x_1, y_1)
+fn line(var: &str, x: Fraction, y: Fraction, m: Fraction) -> Equation {
+	let mut rhs = Expression::Sum(Sum {
+		terms: vec![
+			Box::new(Expression::Product(Product {
+				coefficient: m,
+				factors: vec![Box::new(var.into())],
+			})),
+			Box::new(Expression::Numeral(y - m * x)),
+		],
+	});
+	rhs.simplify();
+	Equation {
+		lhs: Box::new("y".into()),
+		rhs: Box::new(rhs),
+	}
+}
+
+// y - y_1 = m(x - x_1)
+fn point_gradient_form(var: &str, x: Fraction, y: Fraction, m: Fraction) -> String {
+	let difference = |v: &str, k: Fraction| {
+		let mut exp = Expression::Sum(Sum {
+			terms: vec![
+				Box::new(v.into()),
+				Box::new(Expression::Numeral(k.negative())),
+			],
+		});
+		exp.simplify();
+		exp
+	};
+	let rhs = Expression::Product(Product {
+		coefficient: m,
+		factors: vec![Box::new(difference(var, x))],
+	});
+	format!("{} = {}", difference("y", y), rhs)
+}
@@ -0,0 +1,149 @@
+use crate::expression::{Expression, Fraction, Product, SubIn, Sum};
+use std::collections::HashMap;
+use std::fmt;
+pub mod modulus;
+pub mod simultaneous;
+pub mod subject;
+pub use modulus::modulus_argument;
+pub use simultaneous::{solve_simultaneous, solve_simultaneous_with_steps, SimultaneousError};
+pub use subject::make_subject;
+
+#[cfg(test)]
+mod tests {
+	use crate::equation::*;
+	use crate::expression::*;
+	use crate::*;
+
+	#[test]
+	fn equation_display() {
+		let eq = equation!(sum!(prod!(2, "x"), prod!(3, "y")), 7);
+		assert_eq!(eq.to_string(), "2x + 3y = 7");
+		let eq = eq.sub_in("y", &1.into());
+		assert_eq!(eq.to_string(), "2x + 3 = 7");
+	}
+
+	#[test]
+	fn linear_coefficients() {
+		let eq = equation!(
+			sum!(prod!(2, "x"), prod!(-3, sum!("y", 1))),
+			sum!(quotient!("x", 2), 4)
+		);
+		let (coefficients, constant) = eq.linear_coefficients().unwrap();
+		assert_eq!(coefficients["x"], Fraction::new(3, 2));
+		assert_eq!(coefficients["y"], (-3).into());
+		assert_eq!(constant, (-7).into());
+		let eq = equation!(prod!("x", "y"), 1);
+		assert!(eq.linear_coefficients().is_none());
+		let eq = equation!(exp!("x", 2), 1);
+		assert!(eq.linear_coefficients().is_none());
+	}
+}
+
+#[macro_export]
+macro_rules! equation {
+	( $a:expr, $b:expr ) => {
+		Equation {
+			lhs: Box::new($a.into()),
+			rhs: Box::new($b.into()),
+		}
+	};
+}
+
+#[derive(Debug, Clone)]
+pub struct Equation {
+	pub lhs: Box<Expression>,
+	pub rhs: Box<Expression>,
+}
+
+impl fmt::Display for Equation {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{} = {}", self.lhs, self.rhs)
+	}
+}
+
+impl Equation {
+	pub fn sub_in(&self, var: &str, val: &Expression) -> Equation {
+		Equation {
+			lhs: Box::new(self.lhs.sub_in(var, val)),
+			rhs: Box::new(self.rhs.sub_in(var, val)),
+		}
+	}
+
+	// lhs - rhs, expanded and simplified
+	pub fn to_expression(&self) -> Expression {
+		let mut exp = Expression::Sum(Sum {
+			terms: vec![
+				self.lhs.clone(),
+				Box::new(Expression::Product(Product {
+					coefficient: Fraction::from(-1),
+					factors: vec![self.rhs.clone()],
+				})),
+			],
+		});
+		exp.expand_and_simplify();
+		exp
+	}
+
+	// writes the equation as a_1 x_1 + ... + a_n x_n + c = 0
+	// returns None if the equation is not linear
+	pub fn linear_coefficients(&self) -> Option<(HashMap<String, Fraction>, Fraction)> {
+		linear_coefficients(&self.to_expression())
+	}
+}
+
+// coefficients of each variable and the constant term of a linear expression
+// the expression should already be expanded and simplified
+pub fn linear_coefficients(exp: &Expression) -> Option<(HashMap<String, Fraction>, Fraction)> {
+	let mut coefficients: HashMap<String, Fraction> = HashMap::new();
+	let mut constant = Fraction::from(0);
+	collect_linear_terms(exp, Fraction::from(1), &mut coefficients, &mut constant)?;
+	coefficients.retain(|_, c| c.is_nonzero());
+	Some((coefficients, constant))
+}
+
+// adds scale * exp to the coefficients and constant
+// returns None on encountering a term not of the form k, x or kx
+fn collect_linear_terms(
+	exp: &Expression,
+	scale: Fraction,
+	coefficients: &mut HashMap<String, Fraction>,
+	constant: &mut Fraction,
+) -> Option<()> {
+	match exp {
+		Expression::Sum(s) => {
+			for t in s.terms.iter() {
+				collect_linear_terms(t, scale, coefficients, constant)?;
+			}
+		}
+		Expression::Numeral(n) => {
+			*constant = *constant + scale * *n;
+		}
+		Expression::Variable(v) => {
+			let c = coefficients
+				.entry(v.clone())
+				.or_insert_with(|| Fraction::from(0));
+			*c = *c + scale;
+		}
+		Expression::Product(p) => {
+			let scale = scale * p.coefficient;
+			match p.factors.len() {
+				0 => {
+					*constant = *constant + scale;
+				}
+				1 => {
+					collect_linear_terms(&p.factors[0], scale, coefficients, constant)?;
+				}
+				_ => return None,
+			}
+		}
+		Expression::Quotient(q) => {
+			if let Expression::Numeral(d) = q.denominator.as_ref() {
+				collect_linear_terms(&q.numerator, scale / *d, coefficients, constant)?;
+			} else {
+				return None;
+			}
+		}
+		_ => return None,
+	}
+	Some(())
+}
@@ -0,0 +1,101 @@
+use crate::equation::Equation;
+use crate::expression::{Expression, Fn, Fraction, Product};
+
+#[cfg(test)]
+mod tests {
+	use crate::equation::*;
+	use crate::expression::*;
+	use crate::*;
+
+	#[test]
+	fn modulus_cases() {
+		let eq = equation!(abs!(sum!(prod!(2, "x"), -1)), 5);
+		let [a, b] = eq.modulus_cases().unwrap();
+		assert_eq!(a.to_string(), "2x - 1 = 5");
+		assert_eq!(b.to_string(), "2x - 1 = - 5");
+		let eq = equation!("x", 5);
+		assert!(eq.modulus_cases().is_none());
+	}
+
+	#[test]
+	fn solve_modulus() {
+		let eq = equation!(abs!(sum!(prod!(2, "x"), -1)), 5);
+		assert_eq!(eq.solve_modulus("x").unwrap(), vec![(-2).into(), 3.into()]);
+		// the modulus can be on either side
+		let eq = equation!(sum!("x", 3), abs!(sum!(prod!(2, "x"), -3)));
+		assert_eq!(eq.solve_modulus("x").unwrap(), vec![0.into(), 6.into()]);
+		// x = -1 from the second case makes the right side negative
+		let eq = equation!(abs!(sum!("x", 1)), prod!(2, "x"));
+		assert_eq!(eq.solve_modulus("x").unwrap(), vec![1.into()]);
+		let eq = equation!(abs!("x"), -1);
+		assert!(eq.solve_modulus("x").unwrap().is_empty());
+		// true for every x >= 0
+		let eq = equation!(abs!("x"), "x");
+		assert!(eq.solve_modulus("x").is_none());
+		let eq = equation!(abs!(sum!("x", -2)), sum!(2, prod!(-1, "x")));
+		assert!(eq.solve_modulus("x").is_none());
+	}
+}
+
+// the argument of |f|, if the expression is a modulus
+pub fn modulus_argument(exp: &Expression) -> Option<&Expression> {
+	match exp {
+		Expression::Fn(Fn::Abs(a)) => Some(&a.argument),
+		_ => None,
+	}
+}
+
+impl Equation {
+	// |f| = g becomes f = g or f = -g
+	// returns None if neither side is a modulus
+	pub fn modulus_cases(&self) -> Option<[Equation; 2]> {
+		let (f, g) = match modulus_argument(&self.lhs) {
+			Some(f) => (f, self.rhs.as_ref()),
+			None => (modulus_argument(&self.rhs)?, self.lhs.as_ref()),
+		};
+		let mut negative = Expression::Product(Product {
+			coefficient: (-1).into(),
+			factors: vec![Box::new(g.clone())],
+		});
+		negative.simplify();
+		Some([
+			Equation {
+				lhs: Box::new(f.clone()),
+				rhs: Box::new(g.clone()),
+			},
+			Equation {
+				lhs: Box::new(f.clone()),
+				rhs: Box::new(negative),
+			},
+		])
+	}
+
+	// solves |f| = g for linear f and g
+	// solutions of either case that do not satisfy the original equation are rejected
+	// returns None if a case is an identity, as in |x| = x, since the solutions are then a range
+	pub fn solve_modulus(&self, var: &str) -> Option<Vec<Fraction>> {
+		let mut solutions: Vec<Fraction> = Vec::new();
+		for case in self.modulus_cases()? {
+			let (coefficients, constant) = case.linear_coefficients()?;
+			if coefficients.keys().any(|v| v != var) {
+				return None;
+			}
+			let a = match coefficients.get(var) {
+				Some(a) if a.is_nonzero() => *a,
+				// a case that holds for every x, such as x = x from |x| = x,
+				// has a range of solutions rather than a list
+				_ if constant.is_zero() => return None,
+				_ => continue,
+			};
+			let x = constant.negative() / a;
+			let check = self.sub_in(var, &Expression::Numeral(x));
+			let lhs: Option<Fraction> = check.lhs.as_ref().clone().try_into().ok();
+			let rhs: Option<Fraction> = check.rhs.as_ref().clone().try_into().ok();
+			if lhs.is_some() && lhs == rhs && !solutions.contains(&x) {
+				solutions.push(x);
+			}
+		}
+		solutions.sort();
+		Some(solutions)
+	}
+}
@@ -0,0 +1,235 @@
+use crate::equation::Equation;
+use crate::expression::{Expression, Fraction, Product, Sum};
+use std::collections::HashMap;
+
+#[cfg(test)]
+mod tests {
+	use crate::equation::*;
+	use crate::expression::*;
+	use crate::*;
+
+	#[test]
+	fn two_unknowns() {
+		// 2x + 3y = 7, x - y = 1
+		let equations = vec![
+			equation!(sum!(prod!(2, "x"), prod!(3, "y")), 7),
+			equation!(sum!("x", prod!(-1, "y")), 1),
+		];
+		let solution = solve_simultaneous(&equations, &["x", "y"]).unwrap();
+		assert_eq!(solution["x"], 2.into());
+		assert_eq!(solution["y"], 1.into());
+		// x/2 + y = 1, 3x - 2(y - 1) = 0
+		let equations = vec![
+			equation!(sum!(quotient!("x", 2), "y"), 1),
+			equation!(sum!(prod!(3, "x"), prod!(-2, sum!("y", -1))), 0),
+		];
+		let solution = solve_simultaneous(&equations, &["x", "y"]).unwrap();
+		assert_eq!(solution["x"], 0.into());
+		assert_eq!(solution["y"], 1.into());
+	}
+
+	#[test]
+	fn three_unknowns() {
+		let equations = vec![
+			equation!(sum!("x", "y", "z"), 6),
+			equation!(sum!(prod!(2, "x"), prod!(-1, "y"), "z"), 3),
+			equation!(sum!("x", prod!(2, "y"), prod!(-3, "z")), -4),
+		];
+		let solution = solve_simultaneous(&equations, &["x", "y", "z"]).unwrap();
+		assert_eq!(solution["x"], 1.into());
+		assert_eq!(solution["y"], 2.into());
+		assert_eq!(solution["z"], 3.into());
+		let equations = vec![
+			equation!(sum!(prod!(2, "a"), prod!(3, "b")), sum!("c", 1)),
+			equation!(sum!("a", prod!(-1, "c")), "b"),
+			equation!(sum!("a", "b", "c"), 0),
+		];
+		let solution = solve_simultaneous(&equations, &["a", "b", "c"]).unwrap();
+		assert_eq!(solution["a"], 0.into());
+		assert_eq!(solution["b"], Fraction::new(1, 4));
+		assert_eq!(solution["c"], Fraction::new(-1, 4));
+	}
+
+	#[test]
+	fn inconsistent_and_dependent() {
+		let equations = vec![
+			equation!(sum!("x", "y"), 1),
+			equation!(sum!(prod!(2, "x"), prod!(2, "y")), 3),
+		];
+		let result = solve_simultaneous(&equations, &["x", "y"]);
+		assert_eq!(result, Err(SimultaneousError::Inconsistent));
+		let equations = vec![
+			equation!(sum!("x", "y"), 1),
+			equation!(sum!(prod!(2, "x"), prod!(2, "y")), 2),
+		];
+		let result = solve_simultaneous(&equations, &["x", "y"]);
+		assert_eq!(result, Err(SimultaneousError::Dependent));
+		let equations = vec![equation!(prod!("x", "y"), 1), equation!("x", 1)];
+		let result = solve_simultaneous(&equations, &["x", "y"]);
+		assert_eq!(result, Err(SimultaneousError::NonLinear));
+		let equations = vec![equation!(sum!("x", "z"), 1), equation!("x", 1)];
+		let result = solve_simultaneous(&equations, &["x", "y"]);
+		assert_eq!(
+			result,
+			Err(SimultaneousError::UnknownVariable("z".to_string()))
+		);
+	}
+
+	#[test]
+	fn steps() {
+		let equations = vec![
+			equation!(sum!(prod!(2, "x"), prod!(3, "y")), 7),
+			equation!(sum!("x", prod!(-1, "y")), 1),
+		];
+		let mut steps: Vec<String> = Vec::new();
+		let solution = solve_simultaneous_with_steps(&equations, &["x", "y"], &mut steps);
+		assert!(solution.is_ok());
+		assert_eq!(
+			steps,
+			vec![
+				"(1): 2x + 3y = 7",
+				"(2): x - y = 1",
+				"(3) = (2) - \\frac{1}{2}(1): - \\frac{5}{2}y = - \\frac{5}{2}",
+				"(4) = (1) + \\frac{6}{5}(3): 2x = 4",
+				"x = 2",
+				"y = 1",
+			]
+		);
+	}
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SimultaneousError {
+	// a term is not of the form kx
+	NonLinear,
+	// a variable appears that we were not asked to solve for
+	UnknownVariable(String),
+	// no solutions
+	Inconsistent,
+	// infinitely many solutions
+	Dependent,
+}
+
+pub fn solve_simultaneous(
+	equations: &[Equation],
+	variables: &[&str],
+) -> Result<HashMap<String, Fraction>, SimultaneousError> {
+	let mut steps: Vec<String> = Vec::new();
+	solve_simultaneous_with_steps(equations, variables, &mut steps)
+}
+
+// gauss-jordan elimination with exact fractions
+// each elimination is recorded in steps as a labelled equation,
+// followed by the value of each variable
+pub fn solve_simultaneous_with_steps(
+	equations: &[Equation],
+	variables: &[&str],
+	steps: &mut Vec<String>,
+) -> Result<HashMap<String, Fraction>, SimultaneousError> {
+	// each row represents a_1 x_1 + ... + a_n x_n = b
+	let mut rows: Vec<(Vec<Fraction>, Fraction)> = Vec::new();
+	for eq in equations.iter() {
+		let (coefficients, constant) = eq
+			.linear_coefficients()
+			.ok_or(SimultaneousError::NonLinear)?;
+		for v in coefficients.keys() {
+			if !variables.contains(&v.as_str()) {
+				return Err(SimultaneousError::UnknownVariable(v.clone()));
+			}
+		}
+		let row: Vec<Fraction> = variables
+			.iter()
+			.map(|v| {
+				coefficients
+					.get(*v)
+					.cloned()
+					.unwrap_or_else(|| Fraction::from(0))
+			})
+			.collect();
+		rows.push((row, constant.negative()));
+	}
+	let mut labels: Vec<usize> = (1..=rows.len()).collect();
+	for (row, label) in rows.iter().zip(labels.iter()) {
+		steps.push(format!("({}): {}", label, row_equation(row, variables)));
+	}
+	let mut next_label = rows.len() + 1;
+	let mut pivot_row = 0;
+	for col in 0..variables.len() {
+		let pivot = (pivot_row..rows.len()).find(|r| rows[*r].0[col].is_nonzero());
+		let pivot = match pivot {
+			Some(p) => p,
+			None => continue,
+		};
+		rows.swap(pivot_row, pivot);
+		labels.swap(pivot_row, pivot);
+		for r in 0..rows.len() {
+			if r == pivot_row || rows[r].0[col].is_zero() {
+				continue;
+			}
+			let factor = rows[r].0[col] / rows[pivot_row].0[col];
+			let (pivot_coefficients, pivot_constant) = rows[pivot_row].clone();
+			let (coefficients, constant) = &mut rows[r];
+			for (c, p) in coefficients.iter_mut().zip(pivot_coefficients.iter()) {
+				*c = *c - factor * *p;
+			}
+			*constant = *constant - factor * pivot_constant;
+			steps.push(format!(
+				"({}) = ({}) {}({}): {}",
+				next_label,
+				labels[r],
+				signed_factor(&factor),
+				labels[pivot_row],
+				row_equation(&rows[r], variables)
+			));
+			labels[r] = next_label;
+			next_label += 1;
+		}
+		pivot_row += 1;
+	}
+	// remaining rows have no variables left
+	for (_, constant) in rows.iter().skip(pivot_row) {
+		if constant.is_nonzero() {
+			return Err(SimultaneousError::Inconsistent);
+		}
+	}
+	if pivot_row < variables.len() {
+		return Err(SimultaneousError::Dependent);
+	}
+	let mut solution: HashMap<String, Fraction> = HashMap::new();
+	for (coefficients, constant) in rows.iter().take(pivot_row) {
+		let col = coefficients.iter().position(|c| c.is_nonzero()).unwrap();
+		let value = *constant / coefficients[col];
+		steps.push(format!("{} = {}", variables[col], value));
+		solution.insert(variables[col].to_string(), value);
+	}
+	Ok(solution)
+}
+
+fn row_equation(row: &(Vec<Fraction>, Fraction), variables: &[&str]) -> Equation {
+	let mut terms: Vec<Box<Expression>> = Vec::new();
+	for (c, v) in row.0.iter().zip(variables.iter()) {
+		if c.is_nonzero() {
+			terms.push(Box::new(Expression::Product(Product {
+				coefficient: *c,
+				factors: vec![Box::new(Expression::Variable(v.to_string()))],
+			})));
+		}
+	}
+	let mut lhs = Expression::Sum(Sum { terms });
+	lhs.simplify();
+	Equation {
+		lhs: Box::new(lhs),
+		rhs: Box::new(Expression::Numeral(row.1)),
+	}
+}
+
+// "- 2", "+ \frac{1}{2}", "-" for use in "(2) - 2(1)"
+fn signed_factor(factor: &Fraction) -> String {
+	let sign = if factor.is_negative() { "+" } else { "-" };
+	let factor = factor.abs();
+	if factor.is_one() {
+		sign.to_string()
+	} else {
+		format!("{} {}", sign, factor)
+	}
+}
@@ -0,0 +1,305 @@
+use crate::equation::Equation;
+use crate::expression::{Exponent, Expression, Fraction, Product, Quotient, Sum};
+
+#[cfg(test)]
+mod tests {
+	use crate::equation::*;
+	use crate::expression::*;
+	use crate::*;
+
+	#[test]
+	fn linear() {
+		let eq = equation!("y", sum!(prod!(3, "x"), 2));
+		assert_eq!(
+			make_subject(&eq, "x").unwrap().to_string(),
+			"x = \\frac{y - 2}{3}"
+		);
+		// A = P(1 + rt)
+		let eq = equation!("A", prod!("P", sum!(1, prod!("r", "t"))));
+		assert_eq!(
+			make_subject(&eq, "r").unwrap().to_string(),
+			"r = \\frac{A - P}{Pt}"
+		);
+		// v = u + at
+		let eq = equation!("v", sum!("u", prod!("a", "t")));
+		assert_eq!(
+			make_subject(&eq, "a").unwrap().to_string(),
+			"a = \\frac{v - u}{t}"
+		);
+		assert_eq!(make_subject(&eq, "u").unwrap().to_string(), "u = v - at");
+	}
+
+	#[test]
+	fn cross_multiply() {
+		let eq = equation!("y", quotient!(sum!(prod!(3, "x"), 2), sum!("x", -1)));
+		assert_eq!(
+			make_subject(&eq, "x").unwrap().to_string(),
+			"x = \\frac{y + 2}{y - 3}"
+		);
+		let eq = equation!(
+			quotient!(1, "f"),
+			sum!(quotient!(1, "u"), quotient!(1, "v"))
+		);
+		assert_eq!(
+			make_subject(&eq, "u").unwrap().to_string(),
+			"u = \\frac{fv}{v - f}"
+		);
+		let eq = equation!(quotient!("a", sum!("b", "x")), "c");
+		assert_eq!(
+			make_subject(&eq, "x").unwrap().to_string(),
+			"x = \\frac{a - cb}{c}"
+		);
+	}
+
+	#[test]
+	fn squares_and_roots() {
+		// v^2 = u^2 + 2as
+		let eq = equation!(exp!("v", 2), sum!(exp!("u", 2), prod!(2, "a", "s")));
+		assert_eq!(
+			make_subject(&eq, "u").unwrap().to_string(),
+			"u = \\sqrt{v^2 - 2as}"
+		);
+		assert_eq!(
+			make_subject(&eq, "s").unwrap().to_string(),
+			"s = \\frac{v^2 - u^2}{2a}"
+		);
+		// y = 2(x + 1)^(1/2)
+		let eq = equation!("y", prod!(2, exp!(sum!("x", 1), Fraction::new(1, 2))));
+		assert_eq!(
+			make_subject(&eq, "x").unwrap().to_string(),
+			"x = \\frac{y^2 - 4}{4}"
+		);
+		// only the principal root
+		let eq = equation!(exp!("x", 2), "y");
+		assert_eq!(make_subject(&eq, "x").unwrap().to_string(), "x = \\sqrt{y}");
+		let eq = equation!(sum!(exp!("x", 2), "x"), "y");
+		assert!(make_subject(&eq, "x").is_none());
+	}
+}
+
+// rearranges the equation so that var is alone on the left
+// handles var appearing as the same power (e.g. x, x^2, x^(1/2)) in every term
+// after clearing a top-level quotient or square root on either side
+// and multiplying through by any denominators left in the terms
+// for even powers only the principal root is returned, so x^2 = y gives x = \sqrt{y}
+// and the negative root - \sqrt{y} is left to the caller
+// returns None if the equation cannot be rearranged this way
+pub fn make_subject(equation: &Equation, var: &str) -> Option<Equation> {
+	let mut lhs = equation.lhs.as_ref().clone();
+	let mut rhs = equation.rhs.as_ref().clone();
+	lhs.simplify();
+	rhs.simplify();
+	// cross multiply
+	if let Expression::Quotient(q) = lhs.clone() {
+		lhs = *q.numerator;
+		rhs = product(vec![rhs, *q.denominator]);
+	}
+	if let Expression::Quotient(q) = rhs.clone() {
+		rhs = *q.numerator;
+		lhs = product(vec![lhs, *q.denominator]);
+	}
+	// square both sides to remove a square root
+	if is_root_of(&rhs, var) || is_root_of(&lhs, var) {
+		lhs = square(&lhs);
+		rhs = square(&rhs);
+	}
+	let eq = Equation {
+		lhs: Box::new(lhs),
+		rhs: Box::new(rhs),
+	};
+	let mut exp = eq.to_expression();
+	// multiply through by any remaining denominators, e.g. in 1/f = 1/u + 1/v
+	for _ in 0..MAX_DENOMINATORS {
+		let denominator = match terms(&exp).iter().find_map(denominator) {
+			Some(d) => d,
+			None => break,
+		};
+		exp = Expression::Sum(Sum {
+			terms: terms(&exp)
+				.iter()
+				.map(|t| Box::new(clear_denominator(t, &denominator)))
+				.collect(),
+		});
+		exp.expand_and_simplify();
+	}
+	// collect terms into coefficient * var^power + others = 0
+	let mut power: Option<Fraction> = None;
+	let mut coefficient_terms: Vec<Box<Expression>> = Vec::new();
+	let mut var_terms: Vec<Box<Expression>> = Vec::new();
+	let mut other_terms: Vec<Box<Expression>> = Vec::new();
+	for t in terms(&exp) {
+		if !t.contains_variable(var) {
+			other_terms.push(Box::new(negate(&t)));
+			continue;
+		}
+		let (coefficient, pow) = split_power(&t, var)?;
+		if coefficient.contains_variable(var) || power.is_some_and(|p| p != pow) {
+			return None;
+		}
+		power = Some(pow);
+		coefficient_terms.push(Box::new(coefficient));
+		var_terms.push(Box::new(t));
+	}
+	let power = power?;
+	// take var out as a common factor, or add the coefficients if factorize cannot
+	let mut factorised = Expression::Sum(Sum { terms: var_terms });
+	factorised.factorize();
+	let mut coefficient = match split_power(&factorised, var) {
+		Some((coefficient, pow)) if pow == power && !coefficient.contains_variable(var) => coefficient,
+		_ => Expression::Sum(Sum {
+			terms: coefficient_terms,
+		}),
+	};
+	coefficient.simplify();
+	let mut others = Expression::Sum(Sum { terms: other_terms });
+	others.simplify();
+	// keep the denominator positive
+	if leading_coefficient(&coefficient).is_negative() {
+		coefficient = negate(&coefficient);
+		others = negate(&others);
+	}
+	let mut subject = match coefficient {
+		Expression::Numeral(n) if n.is_one() => others,
+		_ => Expression::Quotient(Quotient {
+			numerator: Box::new(others),
+			denominator: Box::new(coefficient),
+		}),
+	};
+	if !power.is_one() {
+		subject = Expression::Exponent(Exponent {
+			base: Box::new(subject),
+			exponent: Box::new(Expression::Numeral(power.reciprocal())),
+		});
+		subject.simplify();
+	}
+	Some(Equation {
+		lhs: Box::new(Expression::Variable(var.to_string())),
+		rhs: Box::new(subject),
+	})
+}
+
+// splits a term into (coefficient, power) where term = coefficient * var^power
+fn split_power(term: &Expression, var: &str) -> Option<(Expression, Fraction)> {
+	match term {
+		Expression::Variable(v) if v == var => Some((Expression::Numeral(1.into()), 1.into())),
+		Expression::Exponent(e) => match (e.base.as_ref(), e.exponent.as_ref()) {
+			(Expression::Variable(v), Expression::Numeral(n)) if v == var => {
+				Some((Expression::Numeral(1.into()), *n))
+			}
+			_ => None,
+		},
+		Expression::Product(p) => {
+			let pow = p.variable_pow(var)?;
+			let mut coefficient = Expression::Product(Product {
+				coefficient: p.coefficient,
+				factors: p.variable_decrement(var, &pow),
+			});
+			coefficient.simplify();
+			Some((coefficient, pow))
+		}
+		_ => None,
+	}
+}
+
+// k f^(1/2) where f contains var
+fn is_root_of(exp: &Expression, var: &str) -> bool {
+	match exp {
+		Expression::Exponent(e) => {
+			e.base.contains_variable(var)
+				&& matches!(e.exponent.as_ref(), Expression::Numeral(n) if *n == Fraction::new(1, 2))
+		}
+		Expression::Product(p) => p.factors.len() == 1 && is_root_of(&p.factors[0], var),
+		_ => false,
+	}
+}
+
+// squares k f^(1/2) into k^2 f, and anything else into (exp)^2
+fn square(exp: &Expression) -> Expression {
+	let mut squared = match exp {
+		Expression::Exponent(e) if matches!(e.exponent.as_ref(), Expression::Numeral(n) if *n == Fraction::new(1, 2)) => {
+			e.base.as_ref().clone()
+		}
+		Expression::Product(p) if p.factors.len() == 1 => Expression::Product(Product {
+			coefficient: p.coefficient.pow(2),
+			factors: vec![Box::new(square(&p.factors[0]))],
+		}),
+		_ => Expression::Exponent(Exponent {
+			base: Box::new(exp.clone()),
+			exponent: Box::new(Expression::Numeral(2.into())),
+		}),
+	};
+	squared.simplify();
+	squared
+}
+
+const MAX_DENOMINATORS: usize = 4;
+
+fn terms(exp: &Expression) -> Vec<Expression> {
+	match exp {
+		Expression::Sum(s) => s.terms.iter().map(|t| t.as_ref().clone()).collect(),
+		_ => vec![exp.clone()],
+	}
+}
+
+// the denominator of a term of the form n/d or k(n/d), if it is not a numeral
+fn denominator(term: &Expression) -> Option<Expression> {
+	match term {
+		Expression::Quotient(q) if !matches!(q.denominator.as_ref(), Expression::Numeral(_)) => {
+			Some(q.denominator.as_ref().clone())
+		}
+		Expression::Product(p) => p.factors.iter().find_map(|f| denominator(f)),
+		_ => None,
+	}
+}
+
+// term * d, cancelling d with a quotient in the term where possible
+fn clear_denominator(term: &Expression, d: &Expression) -> Expression {
+	match term {
+		Expression::Quotient(q) if q.denominator.to_string() == d.to_string() => {
+			q.numerator.as_ref().clone()
+		}
+		Expression::Product(p) => {
+			let mut factors = p.factors.clone();
+			match factors.iter().position(
+				|f| matches!(f.as_ref(), Expression::Quotient(q) if q.denominator.to_string() == d.to_string()),
+			) {
+				Some(i) => *factors[i] = clear_denominator(&factors[i], d),
+				None => factors.push(Box::new(d.clone())),
+			}
+			Expression::Product(Product {
+				coefficient: p.coefficient,
+				factors,
+			})
+		}
+		_ => product(vec![term.clone(), d.clone()]),
+	}
+}
+
+fn product(factors: Vec<Expression>) -> Expression {
+	Expression::Product(Product {
+		coefficient: Fraction::from(1),
+		factors: factors.into_iter().map(Box::new).collect(),
+	})
+}
+
+fn negate(exp: &Expression) -> Expression {
+	let mut exp = Expression::Product(Product {
+		coefficient: Fraction::from(-1),
+		factors: vec![Box::new(exp.clone())],
+	});
+	exp.expand_and_simplify();
+	exp
+}
+
+// coefficient of the first term, used to decide the sign of an expression
+fn leading_coefficient(exp: &Expression) -> Fraction {
+	match exp {
+		Expression::Numeral(n) => *n,
+		Expression::Product(p) => p.coefficient,
+		Expression::Sum(s) => match s.terms.first() {
+			Some(t) => leading_coefficient(t),
+			None => 0.into(),
+		},
+		_ => 1.into(),
+	}
+}
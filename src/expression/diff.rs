@@ -0,0 +1,367 @@
+use crate::expression::{like_term_key, Expression, Product};
+use std::fmt;
+
+#[cfg(test)]
+mod tests {
+	use crate::expression::*;
+	use crate::*;
+
+	fn changes(d: &Diff) -> Vec<String> {
+		d.differences.iter().map(|d| d.to_string()).collect()
+	}
+
+	#[test]
+	fn sum_terms() {
+		let a = sum!(exp!("x", 2), prod!(3, "x"), 2);
+		let b = sum!(exp!("x", 2), prod!(4, "x"), 2);
+		let d = diff(&a, &b);
+		assert_eq!(changes(&d), vec!["changed 3x to 4x at terms[1]"]);
+		assert_eq!(d.highlight_new(), "x^2 + {\\color{red}{4x}} + 2");
+		let b = sum!(exp!("x", 2), -2);
+		let d = diff(&sum!(exp!("x", 2), prod!(-3, "x"), -2), &b);
+		assert_eq!(changes(&d), vec!["removed - 3x at terms[1]"]);
+		assert_eq!(d.highlight_old(), "x^2 - {\\color{red}{3x}} - 2");
+		assert_eq!(d.highlight_new(), "x^2 - 2");
+		let d = diff(&sum!(prod!(2, "x"), -3), &sum!(prod!(2, "x"), 3));
+		assert_eq!(changes(&d), vec!["changed - 3 to 3 at terms[1]"]);
+		assert_eq!(d.highlight_old(), "2x - {\\color{red}{3}}");
+		// terms are matched regardless of order
+		let d = diff(&sum!("x", "y"), &sum!("y", "x", 1));
+		assert_eq!(changes(&d), vec!["added 1 at terms[2]"]);
+	}
+
+	#[test]
+	fn nested() {
+		let a = quotient!(sum!("x", 1), "x");
+		let b = quotient!(sum!("x", 2), "x");
+		let d = diff(&a, &b);
+		assert_eq!(changes(&d), vec!["changed 1 to 2 at numerator.terms[1]"]);
+		assert_eq!(d.highlight_new(), "\\frac{x + {\\color{red}{2}}}{x}");
+		let d = diff(&prod!(exp!("x", 2), "y"), &prod!(exp!("x", 3), "y"));
+		assert_eq!(changes(&d), vec!["changed 2 to 3 at factors[0].exponent"]);
+		assert_eq!(d.highlight_new(), "x^{{\\color{red}{3}}}y");
+		let d = diff(&sin!("x"), &cos!("x"));
+		assert_eq!(changes(&d), vec!["changed \\sin x to \\cos x at root"]);
+		assert!(diff(&sum!("x", 1), &sum!("x", 1)).is_empty());
+	}
+}
+
+// a step from an expression to one of its parts
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Step {
+	Term(usize),
+	Factor(usize),
+	Numerator,
+	Denominator,
+	Base,
+	Exponent,
+}
+
+impl fmt::Display for Step {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Step::Term(i) => write!(f, "terms[{}]", i),
+			Step::Factor(i) => write!(f, "factors[{}]", i),
+			Step::Numerator => write!(f, "numerator"),
+			Step::Denominator => write!(f, "denominator"),
+			Step::Base => write!(f, "base"),
+			Step::Exponent => write!(f, "exponent"),
+		}
+	}
+}
+
+fn path_string(path: &[Step]) -> String {
+	if path.is_empty() {
+		return "root".to_string();
+	}
+	path
+		.iter()
+		.map(|s| s.to_string())
+		.collect::<Vec<String>>()
+		.join(".")
+}
+
+#[derive(Debug, Clone)]
+pub enum Change {
+	Added(Expression),
+	Removed(Expression),
+	Changed(Expression, Expression),
+}
+
+// the old path is in the first expression and the new path in the second
+#[derive(Debug, Clone)]
+pub struct Difference {
+	pub change: Change,
+	pub old_path: Option<Vec<Step>>,
+	pub new_path: Option<Vec<Step>>,
+}
+
+impl fmt::Display for Difference {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let old = self
+			.old_path
+			.as_deref()
+			.map(path_string)
+			.unwrap_or_default();
+		let new = self
+			.new_path
+			.as_deref()
+			.map(path_string)
+			.unwrap_or_default();
+		match &self.change {
+			Change::Added(exp) => write!(f, "added {} at {}", exp, new),
+			Change::Removed(exp) => write!(f, "removed {} at {}", exp, old),
+			Change::Changed(a, b) => write!(f, "changed {} to {} at {}", a, b, new),
+		}
+	}
+}
+
+#[derive(Debug, Clone)]
+pub struct Diff {
+	pub old: Expression,
+	pub new: Expression,
+	pub differences: Vec<Difference>,
+}
+
+// aligns the two trees, matching sum terms by their like term key and product factors by kind
+// a product whose coefficient changes is reported as a whole
+pub fn diff(old: &Expression, new: &Expression) -> Diff {
+	let mut differences = Vec::new();
+	diff_at(old, new, &mut Vec::new(), &mut Vec::new(), &mut differences);
+	Diff {
+		old: old.clone(),
+		new: new.clone(),
+		differences,
+	}
+}
+
+fn diff_at(
+	a: &Expression,
+	b: &Expression,
+	a_path: &mut Vec<Step>,
+	b_path: &mut Vec<Step>,
+	differences: &mut Vec<Difference>,
+) {
+	if a.to_string() == b.to_string() {
+		return;
+	}
+	match (a, b) {
+		(Expression::Sum(x), Expression::Sum(y)) => {
+			let pairs = align(&x.terms, &y.terms, like_term_key);
+			diff_children(
+				&x.terms,
+				&y.terms,
+				pairs,
+				Step::Term,
+				a_path,
+				b_path,
+				differences,
+			);
+		}
+		(Expression::Product(x), Expression::Product(y)) if x.coefficient == y.coefficient => {
+			let pairs = align(&x.factors, &y.factors, kind);
+			diff_children(
+				&x.factors,
+				&y.factors,
+				pairs,
+				Step::Factor,
+				a_path,
+				b_path,
+				differences,
+			);
+		}
+		(Expression::Quotient(x), Expression::Quotient(y)) => {
+			for (step, p, q) in [
+				(Step::Numerator, &x.numerator, &y.numerator),
+				(Step::Denominator, &x.denominator, &y.denominator),
+			] {
+				a_path.push(step);
+				b_path.push(step);
+				diff_at(p, q, a_path, b_path, differences);
+				a_path.pop();
+				b_path.pop();
+			}
+		}
+		(Expression::Exponent(x), Expression::Exponent(y)) => {
+			for (step, p, q) in [
+				(Step::Base, &x.base, &y.base),
+				(Step::Exponent, &x.exponent, &y.exponent),
+			] {
+				a_path.push(step);
+				b_path.push(step);
+				diff_at(p, q, a_path, b_path, differences);
+				a_path.pop();
+				b_path.pop();
+			}
+		}
+		_ => differences.push(Difference {
+			change: Change::Changed(a.clone(), b.clone()),
+			old_path: Some(a_path.clone()),
+			new_path: Some(b_path.clone()),
+		}),
+	}
+}
+
+// the kind of a factor, with powers grouped by base
+fn kind(exp: &Expression) -> String {
+	match exp {
+		Expression::Sum(_) => "sum".to_string(),
+		Expression::Product(_) => "product".to_string(),
+		Expression::Quotient(_) => "quotient".to_string(),
+		Expression::Exponent(e) => format!("exponent {}", e.base),
+		Expression::Variable(v) => format!("exponent {}", v),
+		Expression::Numeral(_) => "numeral".to_string(),
+		Expression::Fn(_) => "fn".to_string(),
+	}
+}
+
+// pairs of (old index, new index), matching equal children first and then children with the same key
+// unmatched children have None on the other side
+fn align<F>(
+	a: &[Box<Expression>],
+	b: &[Box<Expression>],
+	key: F,
+) -> Vec<(Option<usize>, Option<usize>)>
+where
+	F: std::ops::Fn(&Expression) -> String,
+{
+	let mut matched: Vec<Option<usize>> = vec![None; b.len()];
+	let mut used = vec![false; a.len()];
+	let passes: [&dyn std::ops::Fn(&Expression) -> String; 2] =
+		[&|e: &Expression| e.to_string(), &key];
+	for pass in passes {
+		for (j, y) in b.iter().enumerate() {
+			if matched[j].is_some() {
+				continue;
+			}
+			let found = (0..a.len()).find(|i| !used[*i] && pass(&a[*i]) == pass(y));
+			if let Some(i) = found {
+				used[i] = true;
+				matched[j] = Some(i);
+			}
+		}
+	}
+	let mut pairs: Vec<(Option<usize>, Option<usize>)> = Vec::new();
+	for i in (0..a.len()).filter(|i| !used[*i]) {
+		pairs.push((Some(i), None));
+	}
+	for (j, i) in matched.into_iter().enumerate() {
+		pairs.push((i, Some(j)));
+	}
+	pairs
+}
+
+fn diff_children(
+	a: &[Box<Expression>],
+	b: &[Box<Expression>],
+	pairs: Vec<(Option<usize>, Option<usize>)>,
+	step: fn(usize) -> Step,
+	a_path: &mut Vec<Step>,
+	b_path: &mut Vec<Step>,
+	differences: &mut Vec<Difference>,
+) {
+	let with = |path: &[Step], i: usize| {
+		let mut path = path.to_vec();
+		path.push(step(i));
+		path
+	};
+	for pair in pairs {
+		match pair {
+			(Some(i), Some(j)) => {
+				a_path.push(step(i));
+				b_path.push(step(j));
+				diff_at(&a[i], &b[j], a_path, b_path, differences);
+				a_path.pop();
+				b_path.pop();
+			}
+			(Some(i), None) => differences.push(Difference {
+				change: Change::Removed(*a[i].clone()),
+				old_path: Some(with(a_path, i)),
+				new_path: None,
+			}),
+			(None, Some(j)) => differences.push(Difference {
+				change: Change::Added(*b[j].clone()),
+				old_path: None,
+				new_path: Some(with(b_path, j)),
+			}),
+			(None, None) => (),
+		}
+	}
+}
+
+impl Diff {
+	pub fn is_empty(&self) -> bool {
+		self.differences.is_empty()
+	}
+
+	// the old expression with removed and changed parts in red
+	pub fn highlight_old(&self) -> String {
+		let paths: Vec<&Vec<Step>> = self
+			.differences
+			.iter()
+			.filter_map(|d| d.old_path.as_ref())
+			.collect();
+		highlight(&self.old, &paths)
+	}
+
+	// the new expression with added and changed parts in red
+	pub fn highlight_new(&self) -> String {
+		let paths: Vec<&Vec<Step>> = self
+			.differences
+			.iter()
+			.filter_map(|d| d.new_path.as_ref())
+			.collect();
+		highlight(&self.new, &paths)
+	}
+}
+
+// replaces each part with a variable holding its coloured LaTeX
+// negative terms of a sum keep their sign outside the colour so that the sum still reads correctly
+fn highlight(exp: &Expression, paths: &[&Vec<Step>]) -> String {
+	let mut exp = exp.clone();
+	for path in paths {
+		let is_term = matches!(path.last(), Some(Step::Term(_)));
+		if let Some(node) = node_mut(&mut exp, path) {
+			let negative = match node {
+				Expression::Product(p) => p.coefficient.is_negative(),
+				Expression::Numeral(n) => n.is_negative(),
+				_ => false,
+			};
+			if is_term && negative {
+				let mut positive = Expression::Product(Product {
+					coefficient: (-1).into(),
+					factors: vec![Box::new(node.clone())],
+				});
+				positive.simplify();
+				*node = Expression::Product(Product {
+					coefficient: (-1).into(),
+					factors: vec![Box::new(colour(&positive))],
+				});
+			} else {
+				*node = colour(node);
+			}
+		}
+	}
+	exp.to_string()
+}
+
+fn colour(exp: &Expression) -> Expression {
+	Expression::Variable(format!("{{\\color{{red}}{{{}}}}}", exp))
+}
+
+fn node_mut<'a>(exp: &'a mut Expression, path: &[Step]) -> Option<&'a mut Expression> {
+	let (step, rest) = match path.split_first() {
+		Some(parts) => parts,
+		None => return Some(exp),
+	};
+	let child = match (exp, step) {
+		(Expression::Sum(s), Step::Term(i)) => s.terms.get_mut(*i)?.as_mut(),
+		(Expression::Product(p), Step::Factor(i)) => p.factors.get_mut(*i)?.as_mut(),
+		(Expression::Quotient(q), Step::Numerator) => q.numerator.as_mut(),
+		(Expression::Quotient(q), Step::Denominator) => q.denominator.as_mut(),
+		(Expression::Exponent(e), Step::Base) => e.base.as_mut(),
+		(Expression::Exponent(e), Step::Exponent) => e.exponent.as_mut(),
+		_ => return None,
+	};
+	node_mut(child, rest)
+}
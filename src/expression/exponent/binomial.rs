@@ -0,0 +1,323 @@
+use crate::expression::{Exponent, Expression, Fraction, Product, Quotient, Sum};
+use std::fmt;
+
+#[cfg(test)]
+mod tests {
+	use crate::expression::*;
+	use crate::*;
+
+	#[test]
+	fn coefficients() {
+		assert_eq!(binomial_coefficient(5, 2), Some(10.into()));
+		assert_eq!(binomial_coefficient(10, 0), Some(1.into()));
+		assert_eq!(binomial_coefficient(20, 10), Some(184756.into()));
+		assert_eq!(binomial_coefficient(3, 4), Some(0.into()));
+		assert_eq!(binomial_coefficient(34, 2), Some(561.into()));
+		assert_eq!(binomial_coefficient(40, 30), Some(847660528.into()));
+		assert!(binomial_coefficient(34, 17).is_none());
+	}
+
+	#[test]
+	fn expansion() {
+		let b = Binomial::from_expression(&exp!(sum!(1, prod!(2, "x")), 5)).unwrap();
+		assert_eq!(
+			b.expand().unwrap().to_string(),
+			"1 + 10x + 40x^2 + 80x^3 + 80x^4 + 32x^5"
+		);
+		assert_eq!(b.first_terms(3).unwrap().to_string(), "1 + 10x + 40x^2");
+		let b = Binomial::from_expression(&exp!(sum!("x", prod!(-1, "y")), 4)).unwrap();
+		assert_eq!(
+			b.expand().unwrap().to_string(),
+			"x^4 - 4x^3y + 6x^2y^2 - 4xy^3 + y^4"
+		);
+		let b = Binomial::from_expression(&exp!(sum!(prod!(2, "x"), quotient!(1, "x")), 3)).unwrap();
+		assert_eq!(
+			b.expand().unwrap().to_string(),
+			"8x^3 + 12x + \\frac{6}{x} + \\frac{1}{x^3}"
+		);
+		let mut steps = Vec::new();
+		let b = Binomial::from_expression(&exp!(sum!(2, "x"), 6)).unwrap();
+		assert_eq!(
+			b.first_terms_with_steps(3, &mut steps).unwrap().to_string(),
+			"64 + 192x + 240x^2"
+		);
+		assert_eq!(steps, vec!["\\left( 2 + x \\right)^6 = \\binom{6}{0}2^6 + \\binom{6}{1}2^5x + \\binom{6}{2}2^4x^2 + \\dots", "= 64 + 192x + 240x^2 + \\dots"]);
+		assert!(Binomial::from_expression(&exp!(sum!(1, "x"), Fraction::new(1, 2))).is_none());
+	}
+
+	#[test]
+	fn general_term() {
+		let b =
+			Binomial::from_expression(&exp!(sum!(prod!(2, "x"), quotient!(1, exp!("x", 2))), 6)).unwrap();
+		assert_eq!(
+			b.general_term(),
+			"T_{r+1} = \\binom{6}{r}x^{6 - 3r}2^{6 - r}"
+		);
+		assert_eq!(b.term(2).unwrap().to_string(), "240");
+		assert_eq!(b.independent_term("x").unwrap().to_string(), "240");
+		assert_eq!(
+			b.coefficient_of("x", &(-3).into()).unwrap().to_string(),
+			"160"
+		);
+		assert_eq!(b.coefficient_of("x", &1.into()).unwrap().to_string(), "0");
+		let b = Binomial::from_expression(&exp!(sum!("x", -2), 5)).unwrap();
+		assert_eq!(
+			b.general_term(),
+			"T_{r+1} = \\binom{5}{r}x^{5 - r}\\left( - 2 \\right)^r"
+		);
+		assert_eq!(
+			b.coefficient_of("x", &2.into()).unwrap().to_string(),
+			"- 80"
+		);
+		let mut steps = Vec::new();
+		let b = Binomial::from_expression(&exp!(sum!("x", -2), 3)).unwrap();
+		assert_eq!(b.to_string(), "\\left( x - 2 \\right)^3");
+		assert_eq!(
+			b.first_terms_with_steps(2, &mut steps).unwrap().to_string(),
+			"x^3 - 6x^2"
+		);
+		assert_eq!(
+			steps[0],
+			"\\left( x - 2 \\right)^3 = \\binom{3}{0}x^3 + \\binom{3}{1}x^2\\left( - 2 \\right) + \\dots"
+		);
+		let b = Binomial::from_expression(&exp!(sum!(1, "x"), 34)).unwrap();
+		assert!(b.expand().is_none());
+	}
+}
+
+// \binom{n}{r} as an exact fraction
+// returns None if it is too large for a Fraction
+pub fn binomial_coefficient(n: u32, r: u32) -> Option<Fraction> {
+	if r > n {
+		return Some(0.into());
+	}
+	let r = r.min(n - r);
+	// \binom{n}{k} = \binom{n}{k - 1} \frac{n - k + 1}{k}, which is always a whole number
+	let mut c: u64 = 1;
+	for k in 1..=r as u64 {
+		c = c.checked_mul(n as u64 - k + 1)? / k;
+	}
+	Some(i32::try_from(c).ok()?.into())
+}
+
+// (a + b)^n for a positive integer n
+#[derive(Debug, Clone)]
+pub struct Binomial {
+	pub a: Expression,
+	pub b: Expression,
+	pub n: u32,
+}
+
+impl fmt::Display for Binomial {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let exp = Expression::Exponent(Exponent {
+			base: Box::new(Expression::Sum(Sum {
+				terms: vec![Box::new(self.a.clone()), Box::new(self.b.clone())],
+			})),
+			exponent: Box::new(Expression::Numeral((self.n as i32).into())),
+		});
+		write!(f, "{}", exp)
+	}
+}
+
+impl Binomial {
+	// reads (a + b)^n from a sum of two terms raised to a positive integer
+	pub fn from_expression(exp: &Expression) -> Option<Binomial> {
+		let e = match exp {
+			Expression::Exponent(e) => e,
+			_ => return None,
+		};
+		let n = match e.exponent.as_ref() {
+			Expression::Numeral(n) if n.is_integer() && n.is_positive() => n.numerator as u32,
+			_ => return None,
+		};
+		match e.base.as_ref() {
+			Expression::Sum(s) if s.terms.len() == 2 => Some(Binomial {
+				a: s.terms[0].as_ref().clone(),
+				b: s.terms[1].as_ref().clone(),
+				n,
+			}),
+			_ => None,
+		}
+	}
+
+	// the term T_{r + 1} = \binom{n}{r} a^{n - r} b^r
+	// returns None if the binomial coefficient is too large
+	pub fn term(&self, r: u32) -> Option<Expression> {
+		Some(self.collect(
+			binomial_coefficient(self.n, r)?,
+			Expression::Numeral(((self.n - r) as i32).into()),
+			Expression::Numeral((r as i32).into()),
+		))
+	}
+
+	// k a^p b^q as a single fraction, so that powers of x in a and b cancel
+	fn collect(&self, k: Fraction, p: Expression, q: Expression) -> Expression {
+		let (a_numerator, a_denominator) = fraction_parts(&self.a);
+		let (b_numerator, b_denominator) = fraction_parts(&self.b);
+		// powers of 1 are left out
+		let product = |coefficient: Fraction, powers: [(Expression, &Expression); 2]| {
+			Box::new(Expression::Product(Product {
+				coefficient,
+				factors: powers
+					.into_iter()
+					.filter(|(base, _)| !matches!(base, Expression::Numeral(x) if x.is_one()))
+					.map(|(base, exponent)| Box::new(power(&base, exponent.clone())))
+					.collect(),
+			}))
+		};
+		let mut exp = Expression::Quotient(Quotient {
+			numerator: product(k, [(a_numerator, &p), (b_numerator, &q)]),
+			denominator: product(1.into(), [(a_denominator, &p), (b_denominator, &q)]),
+		});
+		exp.simplify();
+		exp
+	}
+
+	// all n + 1 terms
+	pub fn expand(&self) -> Option<Expression> {
+		self.first_terms(self.n + 1)
+	}
+
+	// the first k terms in ascending powers of b
+	pub fn first_terms(&self, k: u32) -> Option<Expression> {
+		self.first_terms_with_steps(k, &mut Vec::new())
+	}
+
+	pub fn first_terms_with_steps(&self, k: u32, steps: &mut Vec<String>) -> Option<Expression> {
+		let k = k.min(self.n + 1);
+		let working: Vec<String> = (0..k)
+			.map(|r| {
+				format!(
+					"\\binom{{{}}}{{{}}}{}{}",
+					self.n,
+					r,
+					bracket_power(&self.a, self.n - r),
+					bracket_power(&self.b, r)
+				)
+			})
+			.collect();
+		let mut terms: Vec<Box<Expression>> = Vec::new();
+		for r in 0..k {
+			terms.push(Box::new(self.term(r)?));
+		}
+		let mut exp = Expression::Sum(Sum { terms });
+		exp.simplify();
+		let ellipsis = if k <= self.n { " + \\dots" } else { "" };
+		steps.push(format!("{} = {}{}", self, working.join(" + "), ellipsis));
+		steps.push(format!("= {}{}", exp, ellipsis));
+		Some(exp)
+	}
+
+	// T_{r + 1} with a symbolic r, using the laws of indices to collect powers
+	pub fn general_term(&self) -> String {
+		let r: Expression = "r".into();
+		let n_minus_r = Expression::Sum(Sum {
+			terms: vec![
+				Box::new(Expression::Numeral((self.n as i32).into())),
+				Box::new(Expression::Product(Product {
+					coefficient: (-1).into(),
+					factors: vec![Box::new(r.clone())],
+				})),
+			],
+		});
+		let exp = self.collect(1.into(), n_minus_r, r);
+		format!("T_{{r+1}} = \\binom{{{}}}{{r}}{}", self.n, exp)
+	}
+
+	// the sum of the terms of the form kx^p, given as k
+	// returns 0 if no term has that power, or None if a binomial coefficient is too large
+	pub fn coefficient_of(&self, var: &str, p: &Fraction) -> Option<Expression> {
+		let mut terms: Vec<Box<Expression>> = Vec::new();
+		for r in 0..=self.n {
+			let term = self.term(r)?;
+			if var_power(&term, var).as_ref() == Some(p) {
+				let mut coefficient = remove_var(&term, var);
+				coefficient.simplify();
+				terms.push(Box::new(coefficient));
+			}
+		}
+		let mut exp = Expression::Sum(Sum { terms });
+		exp.simplify();
+		Some(exp)
+	}
+
+	// the term independent of x
+	pub fn independent_term(&self, var: &str) -> Option<Expression> {
+		self.coefficient_of(var, &0.into())
+	}
+}
+
+// (numerator, denominator), with a denominator of 1 for anything but a quotient
+fn fraction_parts(exp: &Expression) -> (Expression, Expression) {
+	match exp {
+		Expression::Quotient(q) => (q.numerator.as_ref().clone(), q.denominator.as_ref().clone()),
+		_ => (exp.clone(), Expression::Numeral(1.into())),
+	}
+}
+
+// a monomial with every power of var replaced by 1
+fn remove_var(exp: &Expression, var: &str) -> Expression {
+	match exp {
+		Expression::Product(p) => Expression::Product(Product {
+			coefficient: p.coefficient,
+			factors: p
+				.factors
+				.iter()
+				.map(|f| Box::new(remove_var(f, var)))
+				.collect(),
+		}),
+		Expression::Quotient(q) => Expression::Quotient(Quotient {
+			numerator: Box::new(remove_var(&q.numerator, var)),
+			denominator: Box::new(remove_var(&q.denominator, var)),
+		}),
+		_ if exp.contains_variable(var) => Expression::Numeral(1.into()),
+		_ => exp.clone(),
+	}
+}
+
+fn power(base: &Expression, exponent: Expression) -> Expression {
+	let mut exp = Expression::Exponent(Exponent {
+		base: Box::new(base.clone()),
+		exponent: Box::new(exponent),
+	});
+	exp.simplify();
+	exp
+}
+
+// a^k in the working, bracketed unless a is a single variable or positive integer
+fn bracket_power(base: &Expression, k: u32) -> String {
+	let base = match base {
+		Expression::Variable(_) => base.to_string(),
+		Expression::Numeral(n) if n.is_integer() && n.is_nonnegative() => base.to_string(),
+		_ => format!("\\left( {} \\right)", base),
+	};
+	match k {
+		0 => String::new(),
+		1 => base,
+		2..=9 => format!("{}^{}", base, k),
+		_ => format!("{}^{{{}}}", base, k),
+	}
+}
+
+// the power of var in a monomial, with x^{-1} in a denominator counted as negative
+// returns None if var appears in any other way
+fn var_power(exp: &Expression, var: &str) -> Option<Fraction> {
+	if !exp.contains_variable(var) {
+		return Some(0.into());
+	}
+	match exp {
+		Expression::Variable(_) => Some(1.into()),
+		Expression::Exponent(e) => match (e.base.as_ref(), e.exponent.as_ref()) {
+			(Expression::Variable(_), Expression::Numeral(n)) => Some(*n),
+			_ => None,
+		},
+		Expression::Product(p) => p.factors.iter().try_fold(Fraction::from(0), |total, f| {
+			Some(total + var_power(f, var)?)
+		}),
+		Expression::Quotient(q) => {
+			Some(var_power(&q.numerator, var)? - var_power(&q.denominator, var)?)
+		}
+		_ => None,
+	}
+}
@@ -0,0 +1,315 @@
+use crate::expression::{Abs, Exponent, Expression, Fn, Fraction, Product, Quotient, Sum};
+
+#[cfg(test)]
+mod tests {
+	use crate::expression::*;
+	use crate::*;
+
+	#[test]
+	fn multiplication() {
+		let exp = prod!(exp!("x", "a"), exp!("x", "b"));
+		assert_eq!(exp.to_string(), "x^{a + b}");
+		let exp = prod!(3, exp!("x", "n"), "x", "y");
+		assert_eq!(exp.to_string(), "3x^{n + 1}y");
+		let exp = prod!(exp!("x", "n"), exp!("x", prod!(-1, "n")));
+		assert_eq!(exp.to_string(), "1");
+		let exp = prod!(exp!(2, "x"), exp!(3, "x"));
+		assert_eq!(exp.to_string(), "6^x");
+		let exp = prod!(exp!(2, "x"), exp!(2, "y"));
+		assert_eq!(exp.to_string(), "2^{x + y}");
+		// numeral powers are unchanged
+		let exp = prod!(exp!("x", 2), "x", exp!(2, 3));
+		assert_eq!(exp.to_string(), "8x^3");
+	}
+
+	#[test]
+	fn power_of_power() {
+		let mut exp = exp!(exp!("x", "a"), "b");
+		exp.simplify();
+		assert_eq!(exp.to_string(), "x^{ab}");
+		let mut exp = exp!(exp!("x", 2), 3);
+		exp.simplify();
+		assert_eq!(exp.to_string(), "x^6");
+		let mut exp = exp!(exp!("x", "n"), Fraction::new(1, 2));
+		exp.simplify();
+		assert_eq!(exp.to_string(), "x^{\\frac{1}{2}n}");
+		let mut exp = exp!(sqrt!(2), 3);
+		exp.simplify();
+		assert_eq!(exp.to_string(), "2\\sqrt{2}");
+		// even powers under a root lose their sign
+		let mut exp = sqrt!(exp!("x", 2));
+		exp.simplify();
+		assert_eq!(exp.to_string(), "\\left| x \\right|");
+		let mut exp = exp!(exp!("x", 2), Fraction::new(3, 2));
+		exp.simplify();
+		assert_eq!(exp.to_string(), "\\left| x \\right|^3");
+		let mut exp = sqrt!(exp!("x", 4));
+		exp.simplify();
+		assert_eq!(exp.to_string(), "x^2");
+		let mut exp = exp!(exp!("x", 3), Fraction::new(1, 3));
+		exp.simplify();
+		assert_eq!(exp.to_string(), "x");
+	}
+
+	#[test]
+	fn division() {
+		let mut exp = quotient!(exp!("x", "a"), exp!("x", "b"));
+		exp.simplify();
+		assert_eq!(exp.to_string(), "x^{a - b}");
+		let mut exp = quotient!(
+			prod!(6, exp!("a", prod!(2, "n")), "b"),
+			prod!(2, exp!("a", "n"))
+		);
+		exp.simplify();
+		assert_eq!(exp.to_string(), "3a^nb");
+		let mut exp = quotient!(exp!("x", sum!("n", 1)), prod!(4, "x"));
+		exp.simplify();
+		assert_eq!(exp.to_string(), "\\frac{x^n}{4}");
+	}
+
+	#[test]
+	fn common_exponents() {
+		let mut p = Product {
+			coefficient: 2.into(),
+			factors: vec![
+				Box::new(exp!("a", "x")),
+				Box::new("c".into()),
+				Box::new(exp!("b", "x")),
+			],
+		};
+		p.combine_common_exponents();
+		assert_eq!(p.to_string(), "2\\left( ab \\right)^xc");
+	}
+}
+
+// (base, exponent) of a factor, treating x as x^1
+// numeral bases are only included with non-numeral exponents, leaving surds alone
+fn power_parts(exp: &Expression) -> Option<(Expression, Expression)> {
+	match exp {
+		Expression::Variable(_) => Some((exp.clone(), Expression::Numeral(1.into()))),
+		Expression::Exponent(e) => match (e.base.as_ref(), e.exponent.as_ref()) {
+			(Expression::Variable(_), _) => Some((*e.base.clone(), *e.exponent.clone())),
+			(Expression::Numeral(b), exponent) if b.is_positive() && !is_numeral(exponent) => {
+				Some((*e.base.clone(), *e.exponent.clone()))
+			}
+			_ => None,
+		},
+		_ => None,
+	}
+}
+
+fn is_numeral(exp: &Expression) -> bool {
+	matches!(exp, Expression::Numeral(_))
+}
+
+fn power(base: Expression, exponent: Expression) -> Expression {
+	let mut exp = Expression::Exponent(Exponent {
+		base: Box::new(base),
+		exponent: Box::new(exponent),
+	});
+	exp.simplify();
+	exp
+}
+
+fn sum(terms: Vec<Expression>) -> Expression {
+	let mut exp = Expression::Sum(Sum {
+		terms: terms.into_iter().map(Box::new).collect(),
+	});
+	exp.simplify();
+	exp
+}
+
+impl Exponent {
+	// (x^a)^b = x^{ab}
+	// negative numeral bases are left alone as even powers lose their sign
+	// for numeral a and b, an even power under a root gives |x|: (x^2)^{1/2} = |x|
+	// symbolic exponents assume a positive base
+	pub fn nested_power(&self) -> Option<Expression> {
+		if let Expression::Exponent(inner) = self.base.as_ref() {
+			let base = inner.base.as_ref();
+			if matches!(base, Expression::Numeral(n) if n.is_negative()) {
+				return None;
+			}
+			let mut exponent = Expression::Product(Product {
+				coefficient: 1.into(),
+				factors: vec![inner.exponent.clone(), self.exponent.clone()],
+			});
+			exponent.simplify();
+			if let (Expression::Numeral(a), Expression::Numeral(b), false) = (
+				inner.exponent.as_ref(),
+				self.exponent.as_ref(),
+				is_numeral(base),
+			) {
+				let loses_sign = a.numerator % 2 == 0 && !b.is_integer();
+				let even_result =
+					matches!(&exponent, Expression::Numeral(e) if e.is_integer() && e.numerator % 2 == 0);
+				if loses_sign && !even_result {
+					let abs = Expression::Fn(Fn::Abs(Abs {
+						argument: Box::new(base.clone()),
+					}));
+					return Some(power(abs, exponent));
+				}
+			}
+			return Some(power(base.clone(), exponent));
+		}
+		None
+	}
+}
+
+impl Product {
+	// x^a \cdot x^b = x^{a + b} and a^x \cdot b^x = (ab)^x for numerals a and b
+	// numeral powers of variables are already combined in collect_coefficients
+	pub fn collect_symbolic_powers(&mut self) {
+		// base string: (base, exponents, factor indices)
+		let mut groups: Vec<(String, Expression, Vec<Expression>, Vec<usize>)> = Vec::new();
+		for (i, factor) in self.factors.iter().enumerate() {
+			if let Some((base, exponent)) = power_parts(factor) {
+				let key = base.to_string();
+				if let Some(group) = groups.iter_mut().find(|g| g.0 == key) {
+					group.2.push(exponent);
+					group.3.push(i);
+				} else {
+					groups.push((key, base, vec![exponent], vec![i]));
+				}
+			}
+		}
+		let mut removed: Vec<usize> = Vec::new();
+		for (_, base, exponents, indices) in groups {
+			if indices.len() < 2 || exponents.iter().all(is_numeral) {
+				continue;
+			}
+			*self.factors[indices[0]] = power(base, sum(exponents));
+			removed.extend(indices.iter().skip(1));
+		}
+		// exponent string: (product of bases, factor indices)
+		let mut numeral_groups: Vec<(String, Fraction, Vec<usize>)> = Vec::new();
+		for (i, factor) in self.factors.iter().enumerate() {
+			if removed.contains(&i) {
+				continue;
+			}
+			if let Expression::Exponent(e) = factor.as_ref() {
+				if let (Expression::Numeral(b), false) = (e.base.as_ref(), is_numeral(&e.exponent)) {
+					let key = e.exponent.to_string();
+					if let Some(group) = numeral_groups.iter_mut().find(|g| g.0 == key) {
+						group.1 = group.1 * *b;
+						group.2.push(i);
+					} else {
+						numeral_groups.push((key, *b, vec![i]));
+					}
+				}
+			}
+		}
+		for (_, product, indices) in numeral_groups {
+			if indices.len() < 2 {
+				continue;
+			}
+			if let Expression::Exponent(e) = self.factors[indices[0]].as_mut() {
+				*e.base = Expression::Numeral(product);
+			}
+			removed.extend(indices.iter().skip(1));
+		}
+		removed.sort();
+		for i in removed.iter().rev() {
+			self.factors.remove(*i);
+		}
+		// x^0 becomes 1
+		if self.factors.iter().any(|f| is_numeral(f)) {
+			self.collect_coefficients();
+		}
+	}
+
+	// a^x \cdot b^x = (ab)^x for any bases
+	// not part of simplify, which distributes powers over products
+	pub fn combine_common_exponents(&mut self) {
+		// (exponent, product of bases, factor indices)
+		let mut groups: Vec<(Expression, Product, Vec<usize>)> = Vec::new();
+		for (i, factor) in self.factors.iter().enumerate() {
+			if let Expression::Exponent(e) = factor.as_ref() {
+				let key = e.exponent.to_string();
+				if let Some(group) = groups.iter_mut().find(|g| g.0.to_string() == key) {
+					group.1.factors.push(e.base.clone());
+					group.2.push(i);
+				} else {
+					let bases = Product {
+						coefficient: 1.into(),
+						factors: vec![e.base.clone()],
+					};
+					groups.push((*e.exponent.clone(), bases, vec![i]));
+				}
+			}
+		}
+		let mut removed: Vec<usize> = Vec::new();
+		for (exponent, bases, indices) in groups {
+			if indices.len() < 2 {
+				continue;
+			}
+			*self.factors[indices[0]] = Expression::Exponent(Exponent {
+				base: Box::new(Expression::Product(bases)),
+				exponent: Box::new(exponent),
+			});
+			removed.extend(indices.iter().skip(1));
+		}
+		removed.sort();
+		for i in removed.iter().rev() {
+			self.factors.remove(*i);
+		}
+	}
+}
+
+// a numerator or denominator as a product
+fn as_product(exp: &Expression) -> Product {
+	match exp {
+		Expression::Product(p) => p.clone(),
+		Expression::Numeral(n) => Product {
+			coefficient: *n,
+			factors: Vec::new(),
+		},
+		_ => Product {
+			coefficient: 1.into(),
+			factors: vec![Box::new(exp.clone())],
+		},
+	}
+}
+
+impl Quotient {
+	// \frac{x^a}{x^b} = x^{a - b} when either power is not a numeral
+	// numeral powers are cancelled in simplify
+	pub fn cancel_symbolic_powers(&mut self) {
+		let mut numerator = as_product(&self.numerator);
+		let mut denominator = as_product(&self.denominator);
+		let mut changed = false;
+		for factor in numerator.factors.iter_mut() {
+			let (base, a) = match power_parts(factor) {
+				Some(parts) => parts,
+				None => continue,
+			};
+			let found = denominator.factors.iter().enumerate().find_map(|(j, f)| {
+				let (b, c) = power_parts(f)?;
+				if b.to_string() == base.to_string() && !(is_numeral(&a) && is_numeral(&c)) {
+					Some((j, c))
+				} else {
+					None
+				}
+			});
+			if let Some((j, c)) = found {
+				let difference = sum(vec![
+					a,
+					Expression::Product(Product {
+						coefficient: (-1).into(),
+						factors: vec![Box::new(c)],
+					}),
+				]);
+				**factor = power(base, difference);
+				denominator.factors.remove(j);
+				changed = true;
+			}
+		}
+		if changed {
+			numerator.simplify();
+			denominator.simplify();
+			*self.numerator = Expression::Product(numerator);
+			*self.denominator = Expression::Product(denominator);
+			self.simplify();
+		}
+	}
+}
@@ -1,6 +1,9 @@
-use crate::expression::{Expression, SubIn};
+use crate::expression::{Expression, Fn, SubIn, E};
 use std::convert::Into;
 use std::fmt;
+pub mod binomial;
+pub mod indices;
+pub mod surd;
 
 #[cfg(test)]
 mod tests {
@@ -46,6 +49,59 @@ mod tests {
 		let exp = exp.sub_in("z", &4.into());
 		assert_eq!(exp.to_string(), "1694");
 	}
+
+	#[test]
+	fn surds() {
+		assert_eq!(sqrt!(2).to_string(), "\\sqrt{2}");
+		assert_eq!(exp!(5, Fraction::new(1, 3)).to_string(), "\\sqrt[3]{5}");
+		let mut exp = sqrt!(12);
+		exp.simplify();
+		assert_eq!(exp.to_string(), "2\\sqrt{3}");
+		let mut exp = sqrt!(49);
+		exp.simplify();
+		assert_eq!(exp.to_string(), "7");
+		let mut exp = exp!(4, Fraction::new(3, 2));
+		exp.simplify();
+		assert_eq!(exp.to_string(), "8");
+		let mut exp = exp!(-54, Fraction::new(1, 3));
+		exp.simplify();
+		assert_eq!(exp.to_string(), "- 3\\sqrt[3]{2}");
+		// like surds
+		let exp = sum!(prod!(2, sqrt!(3)), prod!(3, sqrt!(3)));
+		assert_eq!(exp.to_string(), "5\\sqrt{3}");
+		let exp = sum!(sqrt!(12), sqrt!(27), 1);
+		assert_eq!(exp.to_string(), "5\\sqrt{3} + 1");
+		let exp = sum!(sqrt!(8), prod!(-1, sqrt!(2)), sqrt!(3));
+		assert_eq!(exp.to_string(), "\\sqrt{2} + \\sqrt{3}");
+		// products of surds
+		let exp = prod!(sqrt!(2), sqrt!(6));
+		assert_eq!(exp.to_string(), "2\\sqrt{3}");
+		let exp = prod!(3, sqrt!(5), sqrt!(5));
+		assert_eq!(exp.to_string(), "15");
+		let exp = prod!(sqrt!(2), "x", sqrt!(3));
+		assert_eq!(exp.to_string(), "\\sqrt{6}x");
+		let exp = prod!(exp!(2, Fraction::new(1, 3)), exp!(4, Fraction::new(1, 3)));
+		assert_eq!(exp.to_string(), "2");
+		let exp = sqrt!(2).sub_in("x", &1.into());
+		assert_eq!(exp.to_string(), "\\sqrt{2}");
+		let exp = sqrt!("x").sub_in("x", &18.into());
+		assert_eq!(exp.to_string(), "3\\sqrt{2}");
+		assert_eq!(sqrt!(sum!("x", 1)).to_string(), "\\sqrt{x + 1}");
+		assert_eq!(exp!("x", Fraction::new(1, 3)).to_string(), "\\sqrt[3]{x}");
+		// zero, one and large prime radicands
+		let mut exp = sqrt!(0);
+		exp.simplify();
+		assert_eq!(exp.to_string(), "0");
+		let mut exp = exp!(0, Fraction::new(3, 2));
+		exp.simplify();
+		assert_eq!(exp.to_string(), "0");
+		let mut exp = sqrt!(1);
+		exp.simplify();
+		assert_eq!(exp.to_string(), "1");
+		let mut exp = sqrt!(2147483647);
+		exp.simplify();
+		assert_eq!(exp.to_string(), "\\sqrt{2147483647}");
+	}
 }
 
 #[derive(Debug, Clone)]
@@ -66,11 +122,36 @@ impl Exponent {
 
 impl fmt::Display for Exponent {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		// surds and roots: \sqrt{2}, \sqrt[3]{2}, \sqrt{x + 1}
+		if let Expression::Numeral(n) = self.exponent.as_ref() {
+			if n.numerator == 1 && n.denominator == 2 {
+				return write!(f, "\\sqrt{{{}}}", self.base);
+			}
+			if n.numerator == 1 && n.denominator > 2 {
+				return write!(f, "\\sqrt[{}]{{{}}}", n.denominator, self.base);
+			}
+		}
+		// trig powers: \sin^2 x
+		if let (Expression::Fn(Fn::Trig(t)), Expression::Numeral(n)) =
+			(self.base.as_ref(), self.exponent.as_ref())
+		{
+			if n.is_integer() && n.is_positive() {
+				return write!(f, "{}", t.power_string(n.numerator));
+			}
+		}
+		// \mathrm{e}^{x}
+		if let Expression::Variable(v) = self.base.as_ref() {
+			if v == E {
+				return write!(f, "{}^{{{}}}", E, self.exponent);
+			}
+		}
 		let brackets = match self.base.as_ref() {
 			Expression::Sum(s) => s.terms.len() > 1,
 			Expression::Product(p) => {
 				p.factors.len() > 1 || (p.coefficient != 1.into() && p.factors.len() == 1)
 			}
+			Expression::Quotient(_) => true,
+			Expression::Numeral(n) => n.is_negative() || !n.is_integer(),
 			_ => false,
 		};
 		// handle brackets for base
@@ -0,0 +1,115 @@
+use crate::expression::{Exponent, Expression, Fraction, Product};
+
+// a^(1/n) where a is a positive integer and n >= 2
+pub fn is_surd(exp: &Expression) -> bool {
+	surd_parts(exp).is_some()
+}
+
+// (radicand, index) of a surd
+pub fn surd_parts(exp: &Expression) -> Option<(i32, u32)> {
+	match exp {
+		Expression::Exponent(e) => e.surd_parts(),
+		_ => None,
+	}
+}
+
+impl Exponent {
+	pub fn surd_parts(&self) -> Option<(i32, u32)> {
+		if let (Expression::Numeral(b), Expression::Numeral(n)) =
+			(self.base.as_ref(), self.exponent.as_ref())
+		{
+			if b.is_integer() && b.is_positive() && n.numerator == 1 && n.denominator > 1 {
+				return Some((b.numerator, n.denominator));
+			}
+		}
+		None
+	}
+}
+
+// b^(p/n) for integer b and fractional p/n
+// takes out perfect nth powers: 12^(1/2) -> 2\sqrt{3}, 4^(3/2) -> 8
+// returns None if there is nothing to simplify
+pub fn simplify_surd(base: &Fraction, exponent: &Fraction) -> Option<Expression> {
+	if !base.is_integer() || exponent.is_integer() || exponent.is_negative() {
+		return None;
+	}
+	if base.is_zero() {
+		return Some(Expression::Numeral(0.into()));
+	}
+	let n = exponent.denominator;
+	// odd roots of negative numbers are negative
+	let sign = if base.is_negative() {
+		if n.is_multiple_of(2) {
+			return None;
+		}
+		-1
+	} else {
+		1
+	};
+	let radicand = base
+		.numerator
+		.abs()
+		.checked_pow(exponent.numerator as u32)?;
+	let (coefficient, radicand) = extract_powers(radicand, n);
+	if radicand == 1 {
+		return Some(Expression::Numeral((sign * coefficient).into()));
+	}
+	if coefficient == 1 && exponent.numerator == 1 && sign == 1 {
+		return None;
+	}
+	let coefficient = Fraction::from(sign * coefficient);
+	let surd = Expression::Exponent(Exponent {
+		base: Box::new(Expression::Numeral(radicand.into())),
+		exponent: Box::new(Expression::Numeral(Fraction::new(1, n as i32))),
+	});
+	if coefficient.is_one() {
+		return Some(surd);
+	}
+	Some(Expression::Product(Product {
+		coefficient,
+		factors: vec![Box::new(surd)],
+	}))
+}
+
+// writes x as k^n * r, returning (k, r)
+fn extract_powers(mut x: i32, n: u32) -> (i32, i32) {
+	let mut coefficient = 1;
+	let mut radicand = 1;
+	let mut p = 2;
+	while p <= x / p {
+		let mut count = 0;
+		while x % p == 0 {
+			x /= p;
+			count += 1;
+		}
+		coefficient *= p.pow(count / n);
+		radicand *= p.pow(count % n);
+		p += 1;
+	}
+	radicand *= x;
+	(coefficient, radicand)
+}
+
+// whether a term is a surd or a multiple of one
+pub fn has_surd(exp: &Expression) -> bool {
+	match exp {
+		Expression::Exponent(e) => e.surd_parts().is_some(),
+		Expression::Product(p) => p.factors.iter().any(|f| is_surd(f)),
+		_ => false,
+	}
+}
+
+// the surd that makes b\sqrt[n]{c} rational when multiplied: c^((n-1)/n)
+pub fn rationalising_factor(exp: &Expression) -> Option<Expression> {
+	let (radicand, index) = match exp {
+		Expression::Product(p) if p.factors.len() == 1 => surd_parts(&p.factors[0])?,
+		_ => surd_parts(exp)?,
+	};
+	Some(Expression::Exponent(Exponent {
+		base: Box::new(Expression::Numeral(radicand.into())),
+		exponent: Box::new(Expression::Numeral(Fraction::new(
+			index as i32 - 1,
+			index as i32,
+		))),
+	}))
+}
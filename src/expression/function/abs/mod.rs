@@ -0,0 +1,104 @@
+use crate::expression::{Expression, Fn, Product, SubIn, E};
+use std::fmt;
+
+#[cfg(test)]
+mod tests {
+	use crate::expression::*;
+	use crate::*;
+
+	#[test]
+	fn abs_display() {
+		assert_eq!(abs!("x").to_string(), "\\left| x \\right|");
+		assert_eq!(
+			abs!(sum!(prod!(2, "x"), -1)).to_string(),
+			"\\left| 2x - 1 \\right|"
+		);
+		assert_eq!(prod!(3, abs!("x")).to_string(), "3\\left| x \\right|");
+	}
+
+	#[test]
+	fn abs_simplify() {
+		let mut exp = abs!(-3);
+		exp.simplify();
+		assert_eq!(exp.to_string(), "3");
+		let mut exp = abs!(prod!(-3, "x"));
+		exp.simplify();
+		assert_eq!(exp.to_string(), "3\\left| x \\right|");
+		let mut exp = abs!(prod!(Fraction::new(-1, 2), "x", "y"));
+		exp.simplify();
+		assert_eq!(exp.to_string(), "\\frac{1}{2}\\left| xy \\right|");
+		let mut exp = abs!(abs!("x"));
+		exp.simplify();
+		assert_eq!(exp.to_string(), "\\left| x \\right|");
+		let mut exp = abs!(exp!("x", 2));
+		exp.simplify();
+		assert_eq!(exp.to_string(), "x^2");
+		let exp = abs!(sum!(prod!(2, "x"), -1)).sub_in("x", &(-3).into());
+		assert_eq!(exp.to_string(), "7");
+		let exp = sum!(abs!("x"), abs!("x"));
+		assert_eq!(exp.to_string(), "2\\left| x \\right|");
+	}
+}
+
+#[macro_export]
+macro_rules! abs {
+	( $x:expr ) => {
+		Expression::Fn(Fn::Abs(Abs {
+			argument: Box::new($x.into()),
+		}))
+	};
+}
+
+#[derive(Debug, Clone)]
+pub struct Abs {
+	pub argument: Box<Expression>,
+}
+
+impl fmt::Display for Abs {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "\\left| {} \\right|", self.argument)
+	}
+}
+
+impl SubIn for Abs {
+	fn sub_in(&self, var: &str, val: &Expression) -> Expression {
+		let mut exp = Expression::Fn(Fn::Abs(Abs {
+			argument: Box::new(self.argument.sub_in(var, val)),
+		}));
+		exp.simplify();
+		exp
+	}
+}
+
+impl Abs {
+	// |-3| = 3, |-3x| = 3|x|, ||x|| = |x|, |x^2| = x^2
+	// returns None if there is nothing to simplify
+	pub fn simplified(&self) -> Option<Expression> {
+		match self.argument.as_ref() {
+			Expression::Numeral(n) => Some(Expression::Numeral(n.abs())),
+			// positive constants
+			Expression::Variable(v) if v == E || v == "\\pi" => Some(self.argument.as_ref().clone()),
+			Expression::Fn(Fn::Abs(_)) => Some(self.argument.as_ref().clone()),
+			Expression::Exponent(e) => match e.exponent.as_ref() {
+				Expression::Numeral(n) if n.is_integer() && n.numerator % 2 == 0 => {
+					Some(self.argument.as_ref().clone())
+				}
+				_ => None,
+			},
+			Expression::Product(p) if !p.coefficient.is_one() && !p.factors.is_empty() => {
+				let mut argument = Expression::Product(Product {
+					coefficient: 1.into(),
+					factors: p.factors.clone(),
+				});
+				argument.simplify();
+				Some(Expression::Product(Product {
+					coefficient: p.coefficient.abs(),
+					factors: vec![Box::new(Expression::Fn(Fn::Abs(Abs {
+						argument: Box::new(argument),
+					})))],
+				}))
+			}
+			_ => None,
+		}
+	}
+}
@@ -0,0 +1,241 @@
+use crate::expression::function::log::Log;
+use crate::expression::{Exponent, Expression, Fn, Fraction, Product, Quotient, Sum};
+
+#[cfg(test)]
+mod tests {
+	use crate::expression::*;
+	use crate::*;
+
+	#[test]
+	fn expand_logs() {
+		let mut exp = log!(2, prod!(8, "x"));
+		exp.expand_logs();
+		assert_eq!(exp.to_string(), "3 + \\log_2 x");
+		let mut exp = ln!(quotient!(exp!("x", 3), "y"));
+		exp.expand_logs();
+		assert_eq!(exp.to_string(), "3\\ln x - \\ln y");
+		let mut exp = log!(10, sqrt!(prod!("a", "b")));
+		exp.expand_logs();
+		assert_eq!(
+			exp.to_string(),
+			"\\frac{1}{2}\\log_{10} a + \\frac{1}{2}\\log_{10} b"
+		);
+	}
+
+	#[test]
+	fn combine_logs() {
+		let mut exp = sum!(log!(2, 12), prod!(-1, log!(2, 3)));
+		exp.combine_logs();
+		assert_eq!(exp.to_string(), "2");
+		let mut exp = sum!(prod!(2, ln!("x")), ln!("y"));
+		exp.combine_logs();
+		assert_eq!(exp.to_string(), "\\ln \\left( x^2y \\right)");
+		let mut exp = sum!(log!(3, "x"), prod!(-2, log!(3, "y")), log!(5, "x"));
+		exp.combine_logs();
+		assert_eq!(
+			exp.to_string(),
+			"\\log_3 \\left( \\frac{x}{y^2} \\right) + \\log_5 x"
+		);
+		// change of base
+		let mut exp = quotient!(log!(10, "x"), log!(10, 2));
+		exp.combine_logs();
+		assert_eq!(exp.to_string(), "\\log_2 x");
+		let exp = match log!(2, "x") {
+			Expression::Fn(Fn::Log(l)) => l.change_base(&E.into()),
+			_ => unreachable!(),
+		};
+		assert_eq!(exp.to_string(), "\\frac{\\ln x}{\\ln 2}");
+	}
+}
+
+impl Expression {
+	// laws of logarithms, splitting arguments apart
+	// \log (ab) = \log a + \log b, \log \frac{a}{b} = \log a - \log b, \log a^n = n\log a
+	pub fn expand_logs(&mut self) {
+		match self {
+			Expression::Sum(s) => {
+				for t in s.terms.iter_mut() {
+					t.expand_logs();
+				}
+			}
+			Expression::Product(p) => {
+				for f in p.factors.iter_mut() {
+					f.expand_logs();
+				}
+			}
+			Expression::Quotient(q) => {
+				q.numerator.expand_logs();
+				q.denominator.expand_logs();
+			}
+			Expression::Exponent(e) => {
+				e.base.expand_logs();
+				e.exponent.expand_logs();
+			}
+			Expression::Fn(Fn::Log(l)) => {
+				if let Some(mut exp) = expand_log(l) {
+					exp.expand_logs();
+					*self = exp;
+				}
+			}
+			_ => (),
+		}
+		self.simplify();
+	}
+
+	// laws of logarithms, combining logs with the same base into a single log
+	// \frac{\log_c x}{\log_c b} is combined into \log_b x
+	pub fn combine_logs(&mut self) {
+		match self {
+			Expression::Sum(s) => {
+				for t in s.terms.iter_mut() {
+					t.combine_logs();
+				}
+				combine_log_terms(s);
+			}
+			Expression::Product(p) => {
+				for f in p.factors.iter_mut() {
+					f.combine_logs();
+				}
+			}
+			Expression::Quotient(q) => {
+				q.numerator.combine_logs();
+				q.denominator.combine_logs();
+				if let (Expression::Fn(Fn::Log(a)), Expression::Fn(Fn::Log(b))) =
+					(q.numerator.as_ref(), q.denominator.as_ref())
+				{
+					if a.base.to_string() == b.base.to_string() {
+						*self = log(&b.argument, a.argument.as_ref().clone());
+					}
+				}
+			}
+			Expression::Exponent(e) => {
+				e.base.combine_logs();
+				e.exponent.combine_logs();
+			}
+			_ => (),
+		}
+		self.simplify();
+	}
+}
+
+fn log(base: &Expression, argument: Expression) -> Expression {
+	Expression::Fn(Fn::Log(Log {
+		base: Box::new(base.clone()),
+		argument: Box::new(argument),
+	}))
+}
+
+// applies one law to a log, if its argument is a product, quotient or power
+fn expand_log(l: &Log) -> Option<Expression> {
+	let base = l.base.as_ref();
+	match l.argument.as_ref() {
+		Expression::Product(p) if p.coefficient.is_positive() => {
+			let mut terms: Vec<Box<Expression>> = Vec::new();
+			if !p.coefficient.is_one() {
+				terms.push(Box::new(log(base, Expression::Numeral(p.coefficient))));
+			}
+			for factor in p.factors.iter() {
+				terms.push(Box::new(log(base, factor.as_ref().clone())));
+			}
+			Some(Expression::Sum(Sum { terms }))
+		}
+		Expression::Quotient(q) => Some(Expression::Sum(Sum {
+			terms: vec![
+				Box::new(log(base, q.numerator.as_ref().clone())),
+				Box::new(Expression::Product(Product {
+					coefficient: (-1).into(),
+					factors: vec![Box::new(log(base, q.denominator.as_ref().clone()))],
+				})),
+			],
+		})),
+		Expression::Exponent(e) => {
+			// the power multiplies every log that the base expands into
+			let mut inner = log(base, e.base.as_ref().clone());
+			inner.expand_logs();
+			let multiply = |exp: Expression| {
+				Box::new(Expression::Product(Product {
+					coefficient: 1.into(),
+					factors: vec![e.exponent.clone(), Box::new(exp)],
+				}))
+			};
+			match inner {
+				Expression::Sum(s) => Some(Expression::Sum(Sum {
+					terms: s.terms.into_iter().map(|t| multiply(*t)).collect(),
+				})),
+				_ => Some(*multiply(inner)),
+			}
+		}
+		_ => None,
+	}
+}
+
+// k\log_b a, returning (k, b, a)
+fn log_term(exp: &Expression) -> Option<(Fraction, Expression, Expression)> {
+	match exp {
+		Expression::Fn(Fn::Log(l)) => Some((1.into(), *l.base.clone(), *l.argument.clone())),
+		Expression::Product(p) if p.factors.len() == 1 => {
+			let (k, base, argument) = log_term(&p.factors[0])?;
+			Some((k * p.coefficient, base, argument))
+		}
+		_ => None,
+	}
+}
+
+// combines logs with the same base, keeping the position of the first one
+fn combine_log_terms(s: &mut Sum) {
+	let mut i = 0;
+	while i < s.terms.len() {
+		let base = match log_term(&s.terms[i]) {
+			Some((_, base, _)) => base,
+			None => {
+				i += 1;
+				continue;
+			}
+		};
+		let indices: Vec<usize> = (i..s.terms.len())
+			.filter(
+				|j| matches!(log_term(&s.terms[*j]), Some((_, b, _)) if b.to_string() == base.to_string()),
+			)
+			.collect();
+		if indices.len() > 1 {
+			let mut numerator: Vec<Box<Expression>> = Vec::new();
+			let mut denominator: Vec<Box<Expression>> = Vec::new();
+			for j in indices.iter() {
+				if let Some((k, _, argument)) = log_term(&s.terms[*j]) {
+					let power = |k: Fraction| {
+						Box::new(Expression::Exponent(Exponent {
+							base: Box::new(argument.clone()),
+							exponent: Box::new(Expression::Numeral(k)),
+						}))
+					};
+					if k.is_negative() {
+						denominator.push(power(k.negative()));
+					} else {
+						numerator.push(power(k));
+					}
+				}
+			}
+			let product = |factors: Vec<Box<Expression>>| {
+				Expression::Product(Product {
+					coefficient: 1.into(),
+					factors,
+				})
+			};
+			let argument = if denominator.is_empty() {
+				product(numerator)
+			} else {
+				Expression::Quotient(Quotient {
+					numerator: Box::new(product(numerator)),
+					denominator: Box::new(product(denominator)),
+				})
+			};
+			let mut combined = log(&base, argument);
+			combined.simplify();
+			for j in indices.iter().skip(1).rev() {
+				s.terms.remove(*j);
+			}
+			*s.terms[i] = combined;
+		}
+		i += 1;
+	}
+}
@@ -0,0 +1,217 @@
+pub mod laws;
+
+use crate::expression::{Expression, Fn, Fraction, SubIn};
+use std::collections::HashMap;
+use std::fmt;
+
+#[cfg(test)]
+mod tests {
+	use crate::expression::*;
+	use crate::*;
+
+	#[test]
+	fn log_display() {
+		assert_eq!(log!(2, "x").to_string(), "\\log_2 x");
+		assert_eq!(log!(10, 5).to_string(), "\\log_{10} 5");
+		assert_eq!(ln!("x").to_string(), "\\ln x");
+		assert_eq!(ln!(sum!("x", 1)).to_string(), "\\ln \\left( x + 1 \\right)");
+		assert_eq!(
+			log!("a", prod!(3, "x")).to_string(),
+			"\\log_a \\left( 3x \\right)"
+		);
+		assert_eq!(exp_e!("x").to_string(), "\\mathrm{e}^{x}");
+		assert_eq!(exp_e!(prod!(2, "x")).to_string(), "\\mathrm{e}^{2x}");
+		assert_eq!(
+			sum!(prod!(2, ln!("x")), prod!(3, ln!("x"))).to_string(),
+			"5\\ln x"
+		);
+	}
+
+	#[test]
+	fn exact_values() {
+		let mut exp = log!(2, 8);
+		exp.simplify();
+		assert_eq!(exp.to_string(), "3");
+		let mut exp = log!(2, Fraction::new(1, 8));
+		exp.simplify();
+		assert_eq!(exp.to_string(), "- 3");
+		let mut exp = log!(8, 4);
+		exp.simplify();
+		assert_eq!(exp.to_string(), "\\frac{2}{3}");
+		let mut exp = log!(Fraction::new(1, 3), 9);
+		exp.simplify();
+		assert_eq!(exp.to_string(), "- 2");
+		let mut exp = log!(10, 1);
+		exp.simplify();
+		assert_eq!(exp.to_string(), "0");
+		let mut exp = log!(2, 6);
+		exp.simplify();
+		assert_eq!(exp.to_string(), "\\log_2 6");
+		let mut exp = log!(2, 2147483647);
+		exp.simplify();
+		assert_eq!(exp.to_string(), "\\log_2 2147483647");
+		let mut exp = ln!(E);
+		exp.simplify();
+		assert_eq!(exp.to_string(), "1");
+		let mut exp = ln!(exp_e!(prod!(3, "x")));
+		exp.simplify();
+		assert_eq!(exp.to_string(), "3x");
+		let mut exp = log!("a", exp!("a", "n"));
+		exp.simplify();
+		assert_eq!(exp.to_string(), "n");
+		let mut exp = exp_e!(ln!("x"));
+		exp.simplify();
+		assert_eq!(exp.to_string(), "x");
+		let exp = log!(3, "x").sub_in("x", &81.into());
+		assert_eq!(exp.to_string(), "4");
+		let exp = exp_e!("x").sub_in("x", &0.into());
+		assert_eq!(exp.to_string(), "1");
+	}
+}
+
+#[macro_export]
+macro_rules! log {
+	( $b:expr, $x:expr ) => {
+		Expression::Fn(Fn::Log(Log {
+			base: Box::new($b.into()),
+			argument: Box::new($x.into()),
+		}))
+	};
+}
+
+#[macro_export]
+macro_rules! ln {
+	( $x:expr ) => {
+		log!(E, $x)
+	};
+}
+
+// \mathrm{e}^x
+#[macro_export]
+macro_rules! exp_e {
+	( $x:expr ) => {
+		exp!(E, $x)
+	};
+}
+
+// the base of natural logarithms
+pub const E: &str = "\\mathrm{e}";
+
+#[derive(Debug, Clone)]
+pub struct Log {
+	pub base: Box<Expression>,
+	pub argument: Box<Expression>,
+}
+
+impl fmt::Display for Log {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let argument = match self.argument.as_ref() {
+			Expression::Variable(v) => v.to_string(),
+			Expression::Numeral(n) if n.is_nonnegative() => n.to_string(),
+			Expression::Fn(Fn::Abs(a)) => a.to_string(),
+			_ => format!("\\left( {} \\right)", self.argument),
+		};
+		if self.is_natural() {
+			return write!(f, "\\ln {}", argument);
+		}
+		let base = self.base.to_string();
+		if base.len() > 1 {
+			write!(f, "\\log_{{{}}} {}", base, argument)
+		} else {
+			write!(f, "\\log_{} {}", base, argument)
+		}
+	}
+}
+
+impl SubIn for Log {
+	fn sub_in(&self, var: &str, val: &Expression) -> Expression {
+		let mut exp = Expression::Fn(Fn::Log(Log {
+			base: Box::new(self.base.sub_in(var, val)),
+			argument: Box::new(self.argument.sub_in(var, val)),
+		}));
+		exp.simplify();
+		exp
+	}
+}
+
+impl Log {
+	// \ln x is \log_{\mathrm{e}} x
+	pub fn is_natural(&self) -> bool {
+		matches!(self.base.as_ref(), Expression::Variable(v) if v == E)
+	}
+
+	// \log_b b^k = k, and \log_b a for numerals where a is a rational power of b
+	// returns None if there is no exact value
+	pub fn exact_value(&self) -> Option<Expression> {
+		let base = self.base.to_string();
+		if self.argument.to_string() == base {
+			return Some(Expression::Numeral(1.into()));
+		}
+		if let Expression::Exponent(e) = self.argument.as_ref() {
+			if e.base.to_string() == base {
+				return Some(e.exponent.as_ref().clone());
+			}
+		}
+		match (self.base.as_ref(), self.argument.as_ref()) {
+			(_, Expression::Numeral(a)) if a.is_one() => Some(Expression::Numeral(0.into())),
+			(Expression::Numeral(b), Expression::Numeral(a)) => exact_log(b, a).map(Expression::Numeral),
+			_ => None,
+		}
+	}
+
+	// \log_b x = \frac{\log_c x}{\log_c b}
+	pub fn change_base(&self, base: &Expression) -> Expression {
+		let log = |argument: &Expression| {
+			Box::new(Expression::Fn(Fn::Log(Log {
+				base: Box::new(base.clone()),
+				argument: Box::new(argument.clone()),
+			})))
+		};
+		let mut exp = Expression::Quotient(crate::expression::Quotient {
+			numerator: log(&self.argument),
+			denominator: log(&self.base),
+		});
+		exp.simplify();
+		exp
+	}
+}
+
+// k such that b^k = a
+// compares prime factorisations so that large powers do not overflow
+fn exact_log(b: &Fraction, a: &Fraction) -> Option<Fraction> {
+	if !b.is_positive() || !a.is_positive() || b.is_one() {
+		return None;
+	}
+	let b_powers = prime_powers(b);
+	let a_powers = prime_powers(a);
+	let (p, n) = b_powers.iter().next()?;
+	let k = Fraction::new(*a_powers.get(p).unwrap_or(&0), *n);
+	let all_match = b_powers.keys().chain(a_powers.keys()).all(|q| {
+		Fraction::from(*a_powers.get(q).unwrap_or(&0)) == k * (*b_powers.get(q).unwrap_or(&0)).into()
+	});
+	if all_match {
+		Some(k)
+	} else {
+		None
+	}
+}
+
+// prime -> power, with negative powers for primes in the denominator
+fn prime_powers(n: &Fraction) -> HashMap<i32, i32> {
+	let mut powers: HashMap<i32, i32> = HashMap::new();
+	let parts = [(n.numerator.abs(), 1), (n.denominator as i32, -1)];
+	for (mut x, sign) in parts {
+		let mut p = 2;
+		while p <= x / p {
+			while x % p == 0 {
+				x /= p;
+				*powers.entry(p).or_insert(0) += sign;
+			}
+			p += 1;
+		}
+		if x > 1 {
+			*powers.entry(x).or_insert(0) += sign;
+		}
+	}
+	powers
+}
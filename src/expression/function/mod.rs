@@ -1,7 +1,17 @@
+pub mod abs;
 pub mod brackets;
+pub mod log;
+pub mod trig;
+pub use abs::Abs;
 pub use brackets::Brackets;
+pub use log::{Log, E};
+pub use trig::identities::TrigIdentity;
+pub use trig::{Trig, TrigFunction};
 
 #[derive(Debug, Clone)]
 pub enum Fn {
 	Brackets(Brackets),
+	Trig(Trig),
+	Log(Log),
+	Abs(Abs),
 }
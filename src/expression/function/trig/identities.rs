@@ -0,0 +1,609 @@
+use crate::expression::function::trig::{Trig, TrigFunction};
+use crate::expression::{Exponent, Expression, Fn, Fraction, Product, Quotient, Sum};
+use std::fmt;
+
+#[cfg(test)]
+mod tests {
+	use crate::expression::*;
+	use crate::*;
+
+	#[test]
+	fn pythagorean() {
+		let mut exp = sum!(exp!(sin!("x"), 2), exp!(cos!("x"), 2));
+		let identities = exp.simplify_trig();
+		assert_eq!(exp.to_string(), "1");
+		assert_eq!(identities, vec![TrigIdentity::Pythagorean]);
+		let mut exp = sum!(
+			prod!(3, exp!(sin!("A"), 2)),
+			"x",
+			prod!(3, exp!(cos!("A"), 2))
+		);
+		exp.simplify_trig();
+		assert_eq!(exp.to_string(), "3 + x");
+		let mut exp = sum!(1, prod!(-1, exp!(sin!("x"), 2)));
+		exp.simplify_trig();
+		assert_eq!(exp.to_string(), "\\cos^2 x");
+		let mut exp = sum!(1, exp!(tan!("x"), 2));
+		exp.simplify_trig();
+		assert_eq!(exp.to_string(), "\\sec^2 x");
+		// different angles are left alone
+		let mut exp = sum!(exp!(sin!("x"), 2), exp!(cos!("y"), 2));
+		assert!(exp.simplify_trig().is_empty());
+		assert_eq!(exp.to_string(), "\\sin^2 x + \\cos^2 y");
+	}
+
+	#[test]
+	fn quotients() {
+		let mut exp = quotient!(sin!("x"), cos!("x"));
+		assert_eq!(exp.simplify_trig(), vec![TrigIdentity::TanQuotient]);
+		assert_eq!(exp.to_string(), "\\tan x");
+		let mut exp = quotient!(prod!(2, cos!("x")), sin!("x"));
+		exp.simplify_trig();
+		assert_eq!(exp.to_string(), "2\\cot x");
+		let mut exp = quotient!(1, cos!("x"));
+		assert_eq!(exp.simplify_trig(), vec![TrigIdentity::Reciprocal]);
+		assert_eq!(exp.to_string(), "\\sec x");
+		let mut exp = tan!("x");
+		assert_eq!(exp.expand_trig(), vec![TrigIdentity::TanQuotient]);
+		assert_eq!(exp.to_string(), "\\frac{\\sin x}{\\cos x}");
+		let mut exp = trig!(TrigFunction::Csc, "x");
+		exp.expand_trig();
+		assert_eq!(exp.to_string(), "\\frac{1}{\\sin x}");
+	}
+
+	#[test]
+	fn double_angle() {
+		let mut exp = prod!(2, sin!("x"), cos!("x"));
+		assert_eq!(exp.simplify_trig(), vec![TrigIdentity::DoubleAngleSin]);
+		assert_eq!(exp.to_string(), "\\sin \\left( 2x \\right)");
+		let mut exp = prod!(6, cos!("A"), sin!("A"));
+		exp.simplify_trig();
+		assert_eq!(exp.to_string(), "3\\sin \\left( 2A \\right)");
+		let mut exp = sum!(exp!(cos!("x"), 2), prod!(-1, exp!(sin!("x"), 2)));
+		assert_eq!(exp.simplify_trig(), vec![TrigIdentity::DoubleAngleCos]);
+		assert_eq!(exp.to_string(), "\\cos \\left( 2x \\right)");
+		let mut exp = sin!(prod!(2, "x"));
+		assert_eq!(exp.expand_trig(), vec![TrigIdentity::DoubleAngleSin]);
+		assert_eq!(exp.to_string(), "2\\sin x\\cos x");
+		let mut exp = cos!(prod!(2, "x"));
+		exp.expand_trig();
+		assert_eq!(exp.to_string(), "\\cos^2 x - \\sin^2 x");
+	}
+
+	#[test]
+	fn addition() {
+		let mut exp = sin!(sum!("A", "B"));
+		assert_eq!(exp.expand_trig(), vec![TrigIdentity::AdditionSin]);
+		assert_eq!(exp.to_string(), "\\sin A\\cos B + \\cos A\\sin B");
+		let mut exp = cos!(sum!("A", prod!(-1, "B")));
+		assert_eq!(exp.expand_trig(), vec![TrigIdentity::AdditionCos]);
+		assert_eq!(exp.to_string(), "\\cos A\\cos B + \\sin A\\sin B");
+		let mut exp = sin!(sum!("x", 30));
+		exp.expand_trig();
+		assert_eq!(
+			exp.to_string(),
+			"\\frac{1}{2}\\sqrt{3}\\sin x + \\frac{1}{2}\\cos x"
+		);
+		let mut exp = sum!(prod!(sin!("A"), cos!("B")), prod!(-1, cos!("A"), sin!("B")));
+		assert_eq!(exp.simplify_trig(), vec![TrigIdentity::AdditionSin]);
+		assert_eq!(exp.to_string(), "\\sin \\left( A - B \\right)");
+		let mut exp = sum!(prod!(cos!("A"), cos!("B")), prod!(-1, sin!("A"), sin!("B")));
+		exp.simplify_trig();
+		assert_eq!(exp.to_string(), "\\cos \\left( A + B \\right)");
+		// either order of factors in either term
+		let mut exp = sum!(prod!(sin!("A"), cos!("B")), prod!(sin!("B"), cos!("A")));
+		assert_eq!(exp.simplify_trig(), vec![TrigIdentity::AdditionSin]);
+		assert_eq!(exp.to_string(), "\\sin \\left( A + B \\right)");
+		let mut exp = sum!(prod!(cos!("B"), sin!("A")), prod!(cos!("A"), sin!("B")));
+		assert_eq!(exp.simplify_trig(), vec![TrigIdentity::AdditionSin]);
+		assert_eq!(exp.to_string(), "\\sin \\left( A + B \\right)");
+	}
+}
+
+// the identities used by simplify_trig and expand_trig
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TrigIdentity {
+	// \sin^2 x + \cos^2 x = 1, 1 + \tan^2 x = \sec^2 x
+	Pythagorean,
+	// \tan x = \frac{\sin x}{\cos x}
+	TanQuotient,
+	// \sec x = \frac{1}{\cos x}, etc
+	Reciprocal,
+	// \sin 2x = 2\sin x\cos x
+	DoubleAngleSin,
+	// \cos 2x = \cos^2 x - \sin^2 x
+	DoubleAngleCos,
+	// \sin (A + B) = \sin A\cos B + \cos A\sin B
+	AdditionSin,
+	// \cos (A + B) = \cos A\cos B - \sin A\sin B
+	AdditionCos,
+}
+
+impl fmt::Display for TrigIdentity {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			TrigIdentity::Pythagorean => write!(f, "\\sin^2 x + \\cos^2 x = 1"),
+			TrigIdentity::TanQuotient => write!(f, "\\tan x = \\frac{{\\sin x}}{{\\cos x}}"),
+			TrigIdentity::Reciprocal => write!(f, "\\sec x = \\frac{{1}}{{\\cos x}}"),
+			TrigIdentity::DoubleAngleSin => write!(f, "\\sin 2x = 2\\sin x\\cos x"),
+			TrigIdentity::DoubleAngleCos => write!(f, "\\cos 2x = \\cos^2 x - \\sin^2 x"),
+			TrigIdentity::AdditionSin => {
+				write!(f, "\\sin (A + B) = \\sin A\\cos B + \\cos A\\sin B")
+			}
+			TrigIdentity::AdditionCos => {
+				write!(f, "\\cos (A + B) = \\cos A\\cos B - \\sin A\\sin B")
+			}
+		}
+	}
+}
+
+impl Expression {
+	// rewrites trig expressions into fewer functions
+	// returns the identities used, in the order they were applied
+	pub fn simplify_trig(&mut self) -> Vec<TrigIdentity> {
+		let mut identities: Vec<TrigIdentity> = Vec::new();
+		self.simplify_trig_recursive(&mut identities);
+		if !identities.is_empty() {
+			self.simplify();
+		}
+		identities
+	}
+
+	// rewrites tan, sec, csc and cot in terms of sin and cos,
+	// and expands double angles and compound angles
+	// returns the identities used, in the order they were applied
+	pub fn expand_trig(&mut self) -> Vec<TrigIdentity> {
+		let mut identities: Vec<TrigIdentity> = Vec::new();
+		self.expand_trig_recursive(&mut identities);
+		if !identities.is_empty() {
+			self.expand_and_simplify();
+		}
+		identities
+	}
+
+	fn simplify_trig_recursive(&mut self, identities: &mut Vec<TrigIdentity>) {
+		match self {
+			Expression::Sum(s) => {
+				for t in s.terms.iter_mut() {
+					t.simplify_trig_recursive(identities);
+				}
+				while let Some(identity) = simplify_trig_sum(s) {
+					identities.push(identity);
+				}
+			}
+			Expression::Product(p) => {
+				for f in p.factors.iter_mut() {
+					f.simplify_trig_recursive(identities);
+				}
+				if simplify_double_angle_product(p) {
+					identities.push(TrigIdentity::DoubleAngleSin);
+				}
+			}
+			Expression::Quotient(q) => {
+				q.numerator.simplify_trig_recursive(identities);
+				q.denominator.simplify_trig_recursive(identities);
+				if let Some((exp, identity)) = simplify_trig_quotient(q) {
+					*self = exp;
+					identities.push(identity);
+				}
+			}
+			Expression::Exponent(e) => {
+				e.base.simplify_trig_recursive(identities);
+				e.exponent.simplify_trig_recursive(identities);
+			}
+			Expression::Fn(Fn::Trig(t)) => {
+				t.argument.simplify_trig_recursive(identities);
+			}
+			_ => (),
+		}
+	}
+
+	fn expand_trig_recursive(&mut self, identities: &mut Vec<TrigIdentity>) {
+		match self {
+			Expression::Sum(s) => {
+				for t in s.terms.iter_mut() {
+					t.expand_trig_recursive(identities);
+				}
+			}
+			Expression::Product(p) => {
+				for f in p.factors.iter_mut() {
+					f.expand_trig_recursive(identities);
+				}
+			}
+			Expression::Quotient(q) => {
+				q.numerator.expand_trig_recursive(identities);
+				q.denominator.expand_trig_recursive(identities);
+			}
+			Expression::Exponent(e) => {
+				e.base.expand_trig_recursive(identities);
+				e.exponent.expand_trig_recursive(identities);
+			}
+			Expression::Fn(Fn::Trig(t)) => {
+				if let Some((mut exp, identity)) = expand_trig_function(t) {
+					identities.push(identity);
+					exp.expand_trig_recursive(identities);
+					*self = exp;
+				}
+			}
+			_ => (),
+		}
+	}
+}
+
+fn trig(function: TrigFunction, argument: &Expression) -> Expression {
+	Expression::Fn(Fn::Trig(Trig {
+		function,
+		argument: Box::new(argument.clone()),
+	}))
+}
+
+fn product(coefficient: Fraction, factors: Vec<Expression>) -> Expression {
+	Expression::Product(Product {
+		coefficient,
+		factors: factors.into_iter().map(Box::new).collect(),
+	})
+}
+
+// k f(A) g(B), with standard angles first so their exact values lead the term
+fn trig_product(k: Fraction, a: Expression, b: Expression) -> Expression {
+	let exact =
+		|exp: &Expression| matches!(exp, Expression::Fn(Fn::Trig(t)) if t.exact_value().is_some());
+	if exact(&b) && !exact(&a) {
+		product(k, vec![b, a])
+	} else {
+		product(k, vec![a, b])
+	}
+}
+
+fn square(exp: Expression) -> Expression {
+	Expression::Exponent(Exponent {
+		base: Box::new(exp),
+		exponent: Box::new(Expression::Numeral(2.into())),
+	})
+}
+
+// k f(u) where f is a trig function, returning (k, function, u)
+fn trig_term(exp: &Expression) -> Option<(Fraction, TrigFunction, Expression)> {
+	match exp {
+		Expression::Fn(Fn::Trig(t)) => Some((1.into(), t.function, *t.argument.clone())),
+		Expression::Product(p) if p.factors.len() == 1 => {
+			let (k, function, argument) = trig_term(&p.factors[0])?;
+			Some((k * p.coefficient, function, argument))
+		}
+		_ => None,
+	}
+}
+
+// k f(u)^2, returning (k, function, u)
+fn trig_square_term(exp: &Expression) -> Option<(Fraction, TrigFunction, Expression)> {
+	match exp {
+		Expression::Exponent(e) => match (e.base.as_ref(), e.exponent.as_ref()) {
+			(Expression::Fn(Fn::Trig(t)), Expression::Numeral(n)) if *n == 2.into() => {
+				Some((1.into(), t.function, *t.argument.clone()))
+			}
+			_ => None,
+		},
+		Expression::Product(p) if p.factors.len() == 1 => {
+			let (k, function, argument) = trig_square_term(&p.factors[0])?;
+			Some((k * p.coefficient, function, argument))
+		}
+		_ => None,
+	}
+}
+
+// k f(A) g(B), returning (k, f, A, g, B)
+fn trig_pair_term(
+	exp: &Expression,
+) -> Option<(Fraction, TrigFunction, Expression, TrigFunction, Expression)> {
+	if let Expression::Product(p) = exp {
+		if let [a, b] = p.factors.as_slice() {
+			if let (Expression::Fn(Fn::Trig(a)), Expression::Fn(Fn::Trig(b))) = (a.as_ref(), b.as_ref()) {
+				return Some((
+					p.coefficient,
+					a.function,
+					*a.argument.clone(),
+					b.function,
+					*b.argument.clone(),
+				));
+			}
+		}
+	}
+	None
+}
+
+// applies one identity to the terms of a sum, if possible
+fn simplify_trig_sum(s: &mut Sum) -> Option<TrigIdentity> {
+	let n = s.terms.len();
+	for i in 0..n {
+		for j in 0..n {
+			if i == j {
+				continue;
+			}
+			if let Some((exp, identity)) = pair_identity(&s.terms[i], &s.terms[j]) {
+				let (first, second) = if i < j { (i, j) } else { (j, i) };
+				s.terms.remove(second);
+				*s.terms[first] = exp;
+				return Some(identity);
+			}
+		}
+	}
+	None
+}
+
+// identities involving two terms of a sum
+fn pair_identity(a: &Expression, b: &Expression) -> Option<(Expression, TrigIdentity)> {
+	use TrigFunction::*;
+	// k\sin^2 u + k\cos^2 u = k, k\cos^2 u - k\sin^2 u = k\cos 2u
+	if let (Some((k1, f1, u1)), Some((k2, f2, u2))) = (trig_square_term(a), trig_square_term(b)) {
+		if u1.to_string() == u2.to_string() {
+			if f1 == Sin && f2 == Cos && k1 == k2 {
+				return Some((Expression::Numeral(k1), TrigIdentity::Pythagorean));
+			}
+			if f1 == Cos && f2 == Sin && k1 == k2.negative() {
+				let double = double_angle(&u1);
+				return Some((
+					product(k1, vec![trig(Cos, &double)]),
+					TrigIdentity::DoubleAngleCos,
+				));
+			}
+		}
+	}
+	// k - k\sin^2 u = k\cos^2 u, k + k\tan^2 u = k\sec^2 u
+	if let (Expression::Numeral(k), Some((k2, f, u))) = (a, trig_square_term(b)) {
+		let result = match f {
+			Sin if k2 == k.negative() => Some(Cos),
+			Cos if k2 == k.negative() => Some(Sin),
+			Tan if k2 == *k => Some(Sec),
+			Cot if k2 == *k => Some(Csc),
+			_ => None,
+		};
+		if let Some(g) = result {
+			return Some((
+				product(*k, vec![square(trig(g, &u))]),
+				TrigIdentity::Pythagorean,
+			));
+		}
+	}
+	// k\sin A\cos B + k\cos A\sin B = k\sin(A + B)
+	// k\cos A\cos B - k\sin A\sin B = k\cos(A + B)
+	if let (Some((k1, f1, a1, g1, b1)), Some((k2, f2, a2, g2, b2))) =
+		(trig_pair_term(a), trig_pair_term(b))
+	{
+		// write each term as k\sin A\cos B, whichever order the factors are in
+		let sin_cos = |f: TrigFunction, a: &Expression, g: TrigFunction, b: &Expression| match (f, g) {
+			(Sin, Cos) => Some((a.clone(), b.clone())),
+			(Cos, Sin) => Some((b.clone(), a.clone())),
+			_ => None,
+		};
+		if let (Some((s1, c1)), Some((s2, c2))) = (sin_cos(f1, &a1, g1, &b1), sin_cos(f2, &a2, g2, &b2))
+		{
+			// k\sin A\cos B + m\sin B\cos A with m = +- k
+			if s1.to_string() == c2.to_string() && c1.to_string() == s2.to_string() {
+				if k1 == k2 {
+					let angle = angle_sum(&s1, &c1, false);
+					return Some((
+						product(k1, vec![trig(Sin, &angle)]),
+						TrigIdentity::AdditionSin,
+					));
+				}
+				if k1 == k2.negative() {
+					let angle = angle_sum(&s1, &c1, true);
+					return Some((
+						product(k1, vec![trig(Sin, &angle)]),
+						TrigIdentity::AdditionSin,
+					));
+				}
+			}
+		}
+		let cos_product = f1 == Cos && g1 == Cos && f2 == Sin && g2 == Sin;
+		let pair_angles = (a1.to_string() == a2.to_string() && b1.to_string() == b2.to_string())
+			|| (a1.to_string() == b2.to_string() && b1.to_string() == a2.to_string());
+		if cos_product && pair_angles {
+			if k1 == k2.negative() {
+				let angle = angle_sum(&a1, &b1, false);
+				return Some((
+					product(k1, vec![trig(Cos, &angle)]),
+					TrigIdentity::AdditionCos,
+				));
+			}
+			if k1 == k2 {
+				let angle = angle_sum(&a1, &b1, true);
+				return Some((
+					product(k1, vec![trig(Cos, &angle)]),
+					TrigIdentity::AdditionCos,
+				));
+			}
+		}
+	}
+	None
+}
+
+fn double_angle(u: &Expression) -> Expression {
+	let mut exp = product(2.into(), vec![u.clone()]);
+	exp.simplify();
+	exp
+}
+
+// A + B or A - B
+fn angle_sum(a: &Expression, b: &Expression, difference: bool) -> Expression {
+	let b = if difference {
+		product(Fraction::from(-1), vec![b.clone()])
+	} else {
+		b.clone()
+	};
+	let mut exp = Expression::Sum(Sum {
+		terms: vec![Box::new(a.clone()), Box::new(b)],
+	});
+	exp.simplify();
+	exp
+}
+
+// k\sin u\cos u = \frac{k}{2}\sin 2u when k is even
+fn simplify_double_angle_product(p: &mut Product) -> bool {
+	if !(p.coefficient / 2.into()).is_integer() {
+		return false;
+	}
+	let sin = p.factors.iter().position(|f| is_trig(f, TrigFunction::Sin));
+	let cos = p.factors.iter().position(|f| is_trig(f, TrigFunction::Cos));
+	if let (Some(i), Some(j)) = (sin, cos) {
+		let (u, v) = match (p.factors[i].as_ref(), p.factors[j].as_ref()) {
+			(Expression::Fn(Fn::Trig(a)), Expression::Fn(Fn::Trig(b))) => {
+				(*a.argument.clone(), *b.argument.clone())
+			}
+			_ => return false,
+		};
+		if u.to_string() != v.to_string() {
+			return false;
+		}
+		p.coefficient = p.coefficient / 2.into();
+		*p.factors[i] = trig(TrigFunction::Sin, &double_angle(&u));
+		p.factors.remove(j);
+		return true;
+	}
+	false
+}
+
+fn is_trig(exp: &Expression, function: TrigFunction) -> bool {
+	matches!(exp, Expression::Fn(Fn::Trig(t)) if t.function == function)
+}
+
+// \frac{k\sin u}{\cos u} = k\tan u, \frac{k}{\cos u} = k\sec u
+fn simplify_trig_quotient(q: &Quotient) -> Option<(Expression, TrigIdentity)> {
+	use TrigFunction::*;
+	let (_, g, v) = trig_term(&q.denominator)?;
+	let denominator_coefficient = match q.denominator.as_ref() {
+		Expression::Product(p) => p.coefficient,
+		_ => 1.into(),
+	};
+	if let Expression::Numeral(k) = q.numerator.as_ref() {
+		let reciprocal = match g {
+			Sin => Csc,
+			Cos => Sec,
+			Tan => Cot,
+			Sec => Cos,
+			Csc => Sin,
+			Cot => Tan,
+		};
+		return Some((
+			product(*k / denominator_coefficient, vec![trig(reciprocal, &v)]),
+			TrigIdentity::Reciprocal,
+		));
+	}
+	let (k, f, u) = trig_term(&q.numerator)?;
+	if u.to_string() != v.to_string() {
+		return None;
+	}
+	let function = match (f, g) {
+		(Sin, Cos) => Tan,
+		(Cos, Sin) => Cot,
+		_ => return None,
+	};
+	Some((
+		product(k / denominator_coefficient, vec![trig(function, &u)]),
+		TrigIdentity::TanQuotient,
+	))
+}
+
+// rewrites a single trig function using one identity
+fn expand_trig_function(t: &Trig) -> Option<(Expression, TrigIdentity)> {
+	use TrigFunction::*;
+	let u = t.argument.as_ref();
+	let reciprocal = |f: TrigFunction| {
+		Expression::Quotient(Quotient {
+			numerator: Box::new(Expression::Numeral(1.into())),
+			denominator: Box::new(trig(f, u)),
+		})
+	};
+	match t.function {
+		Tan => {
+			return Some((
+				Expression::Quotient(Quotient {
+					numerator: Box::new(trig(Sin, u)),
+					denominator: Box::new(trig(Cos, u)),
+				}),
+				TrigIdentity::TanQuotient,
+			));
+		}
+		Cot => {
+			return Some((
+				Expression::Quotient(Quotient {
+					numerator: Box::new(trig(Cos, u)),
+					denominator: Box::new(trig(Sin, u)),
+				}),
+				TrigIdentity::TanQuotient,
+			));
+		}
+		Sec => return Some((reciprocal(Cos), TrigIdentity::Reciprocal)),
+		Csc => return Some((reciprocal(Sin), TrigIdentity::Reciprocal)),
+		_ => (),
+	}
+	// double angles
+	if let Expression::Product(p) = u {
+		if p.coefficient == 2.into() {
+			let mut half = Expression::Product(Product {
+				coefficient: 1.into(),
+				factors: p.factors.clone(),
+			});
+			half.simplify();
+			return match t.function {
+				Sin => Some((
+					product(2.into(), vec![trig(Sin, &half), trig(Cos, &half)]),
+					TrigIdentity::DoubleAngleSin,
+				)),
+				_ => Some((
+					Expression::Sum(Sum {
+						terms: vec![
+							Box::new(square(trig(Cos, &half))),
+							Box::new(product(Fraction::from(-1), vec![square(trig(Sin, &half))])),
+						],
+					}),
+					TrigIdentity::DoubleAngleCos,
+				)),
+			};
+		}
+	}
+	// compound angles
+	if let Expression::Sum(s) = u {
+		if s.terms.len() < 2 {
+			return None;
+		}
+		let a = s.terms[0].as_ref();
+		let mut b = Expression::Sum(Sum {
+			terms: s.terms[1..].to_vec(),
+		});
+		b.simplify();
+		// \sin(-B) = -\sin B, \cos(-B) = \cos B
+		let (b, sign) = match b {
+			Expression::Product(p) if p.coefficient.is_negative() => {
+				let mut b = Expression::Product(p.negative());
+				b.simplify();
+				(b, Fraction::from(-1))
+			}
+			Expression::Numeral(n) if n.is_negative() => {
+				(Expression::Numeral(n.abs()), Fraction::from(-1))
+			}
+			_ => (b, Fraction::from(1)),
+		};
+		return match t.function {
+			Sin => Some((
+				Expression::Sum(Sum {
+					terms: vec![
+						Box::new(trig_product(1.into(), trig(Sin, a), trig(Cos, &b))),
+						Box::new(trig_product(sign, trig(Cos, a), trig(Sin, &b))),
+					],
+				}),
+				TrigIdentity::AdditionSin,
+			)),
+			_ => Some((
+				Expression::Sum(Sum {
+					terms: vec![
+						Box::new(trig_product(1.into(), trig(Cos, a), trig(Cos, &b))),
+						Box::new(trig_product(sign.negative(), trig(Sin, a), trig(Sin, &b))),
+					],
+				}),
+				TrigIdentity::AdditionCos,
+			)),
+		};
+	}
+	None
+}
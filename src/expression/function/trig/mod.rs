@@ -0,0 +1,257 @@
+pub mod identities;
+
+use crate::expression::{Exponent, Expression, Fn, Fraction, Product, SubIn};
+use std::fmt;
+
+#[cfg(test)]
+mod tests {
+	use crate::expression::*;
+	use crate::*;
+
+	#[test]
+	fn trig_display() {
+		assert_eq!(sin!("x").to_string(), "\\sin x");
+		assert_eq!(cos!(prod!(2, "x")).to_string(), "\\cos \\left( 2x \\right)");
+		assert_eq!(tan!(30).to_string(), "\\tan 30^\\circ");
+		assert_eq!(
+			trig!(TrigFunction::Csc, sum!("x", 1)).to_string(),
+			"\\csc \\left( x + 1 \\right)"
+		);
+		assert_eq!(exp!(sin!("x"), 2).to_string(), "\\sin^2 x");
+		assert_eq!(
+			exp!(cos!(prod!(2, "x")), 3).to_string(),
+			"\\cos^3 \\left( 2x \\right)"
+		);
+		assert_eq!(
+			sum!(prod!(2, sin!("x")), prod!(-1, cos!("x"))).to_string(),
+			"2\\sin x - \\cos x"
+		);
+	}
+
+	#[test]
+	fn exact_values() {
+		let exp = sin!("x").sub_in("x", &30.into());
+		assert_eq!(exp.to_string(), "\\frac{1}{2}");
+		let exp = cos!("x").sub_in("x", &prod!(Fraction::new(1, 6), "\\pi"));
+		assert_eq!(exp.to_string(), "\\frac{1}{2}\\sqrt{3}");
+		let exp = tan!("x").sub_in("x", &135.into());
+		assert_eq!(exp.to_string(), "- 1");
+		let exp = tan!("x").sub_in("x", &60.into());
+		assert_eq!(exp.to_string(), "\\sqrt{3}");
+		let exp = tan!("x").sub_in("x", &210.into());
+		assert_eq!(exp.to_string(), "\\frac{1}{3}\\sqrt{3}");
+		let exp = trig!(TrigFunction::Sec, "x").sub_in("x", &(-45).into());
+		assert_eq!(exp.to_string(), "\\sqrt{2}");
+		let exp = trig!(TrigFunction::Cot, "x").sub_in("x", &"\\pi".into());
+		assert_eq!(exp.to_string(), "\\cot \\pi");
+		let exp = tan!("x").sub_in("x", &90.into());
+		assert_eq!(exp.to_string(), "\\tan 90^\\circ");
+		let exp = sin!("x").sub_in("x", &20.into());
+		assert_eq!(exp.to_string(), "\\sin 20^\\circ");
+		let exp = cos!("x").sub_in("x", &quotient!("\\pi", 6));
+		assert_eq!(exp.to_string(), "\\frac{1}{2}\\sqrt{3}");
+		let exp = sin!("x").sub_in("x", &quotient!(prod!(3, "\\pi"), 4));
+		assert_eq!(exp.to_string(), "\\frac{1}{2}\\sqrt{2}");
+		assert_eq!(sin!(-30).to_string(), "\\sin \\left( - 30^\\circ \\right)");
+		let mut exp = prod!(4, sin!(30), cos!(0));
+		exp.simplify();
+		assert_eq!(exp.to_string(), "2");
+	}
+
+	#[test]
+	fn like_terms() {
+		let exp = sum!(prod!(2, sin!("x")), prod!(3, sin!("x")));
+		assert_eq!(exp.to_string(), "5\\sin x");
+		let exp = sum!(sin!("x"), prod!(3, cos!("x")), prod!(-2, sin!("x")));
+		assert_eq!(exp.to_string(), "- \\sin x + 3\\cos x");
+		let exp = sum!(prod!(2, sin!("x")), prod!(3, sin!("y")));
+		assert_eq!(exp.to_string(), "2\\sin x + 3\\sin y");
+	}
+}
+
+#[macro_export]
+macro_rules! trig {
+	( $f:expr, $x:expr ) => {
+		Expression::Fn(Fn::Trig(Trig {
+			function: $f,
+			argument: Box::new($x.into()),
+		}))
+	};
+}
+
+#[macro_export]
+macro_rules! sin {
+	( $x:expr ) => {
+		trig!(TrigFunction::Sin, $x)
+	};
+}
+
+#[macro_export]
+macro_rules! cos {
+	( $x:expr ) => {
+		trig!(TrigFunction::Cos, $x)
+	};
+}
+
+#[macro_export]
+macro_rules! tan {
+	( $x:expr ) => {
+		trig!(TrigFunction::Tan, $x)
+	};
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TrigFunction {
+	Sin,
+	Cos,
+	Tan,
+	Sec,
+	Csc,
+	Cot,
+}
+
+impl fmt::Display for TrigFunction {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			TrigFunction::Sin => write!(f, "\\sin"),
+			TrigFunction::Cos => write!(f, "\\cos"),
+			TrigFunction::Tan => write!(f, "\\tan"),
+			TrigFunction::Sec => write!(f, "\\sec"),
+			TrigFunction::Csc => write!(f, "\\csc"),
+			TrigFunction::Cot => write!(f, "\\cot"),
+		}
+	}
+}
+
+// numeral arguments are in degrees
+// radians are written as multiples of the variable \pi
+#[derive(Debug, Clone)]
+pub struct Trig {
+	pub function: TrigFunction,
+	pub argument: Box<Expression>,
+}
+
+impl fmt::Display for Trig {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{} {}", self.function, self.argument_string())
+	}
+}
+
+impl SubIn for Trig {
+	fn sub_in(&self, var: &str, val: &Expression) -> Expression {
+		let mut exp = Expression::Fn(Fn::Trig(Trig {
+			function: self.function,
+			argument: Box::new(self.argument.sub_in(var, val)),
+		}));
+		exp.simplify();
+		exp
+	}
+}
+
+impl Trig {
+	// \sin^2 x
+	pub fn power_string(&self, power: i32) -> String {
+		let power = if power > 9 {
+			format!("{{{}}}", power)
+		} else {
+			power.to_string()
+		};
+		format!("{}^{} {}", self.function, power, self.argument_string())
+	}
+
+	fn argument_string(&self) -> String {
+		match self.argument.as_ref() {
+			Expression::Variable(v) => v.to_string(),
+			Expression::Numeral(n) if n.is_nonnegative() => format!("{}^\\circ", n),
+			Expression::Numeral(n) => format!("\\left( {}^\\circ \\right)", n),
+			_ => format!("\\left( {} \\right)", self.argument),
+		}
+	}
+
+	// the argument in degrees, if it is a numeral or a multiple of \pi
+	pub fn degrees(&self) -> Option<Fraction> {
+		match self.argument.as_ref() {
+			Expression::Numeral(n) => Some(*n),
+			exp => radians_to_degrees(exp),
+		}
+	}
+
+	// exact values at multiples of 30 and 45 degrees
+	// returns None if the angle is not standard or the function is undefined there
+	pub fn exact_value(&self) -> Option<Expression> {
+		let degrees = self.degrees()?;
+		let sin = sin_value(degrees)?;
+		let cos = sin_value(degrees + 90.into())?;
+		let value = match self.function {
+			TrigFunction::Sin => sin,
+			TrigFunction::Cos => cos,
+			TrigFunction::Tan => divide(sin, cos)?,
+			TrigFunction::Sec => divide((1.into(), 1), cos)?,
+			TrigFunction::Csc => divide((1.into(), 1), sin)?,
+			TrigFunction::Cot => divide(cos, sin)?,
+		};
+		let (coefficient, radicand) = value;
+		let mut exp = if radicand == 1 {
+			Expression::Numeral(coefficient)
+		} else {
+			Expression::Product(Product {
+				coefficient,
+				factors: vec![Box::new(Expression::Exponent(Exponent {
+					base: Box::new(Expression::Numeral(radicand.into())),
+					exponent: Box::new(Expression::Numeral(Fraction::new(1, 2))),
+				}))],
+			})
+		};
+		exp.simplify();
+		Some(exp)
+	}
+}
+
+// exact values are represented as (c, r) for c\sqrt{r}
+fn sin_value(degrees: Fraction) -> Option<(Fraction, i32)> {
+	if !degrees.is_integer() {
+		return None;
+	}
+	let angle = degrees.numerator.rem_euclid(360);
+	// reference angle in the first quadrant
+	let (reference, sign) = match angle {
+		0..=90 => (angle, 1),
+		91..=180 => (180 - angle, 1),
+		181..=270 => (angle - 180, -1),
+		_ => (360 - angle, -1),
+	};
+	let (coefficient, radicand) = match reference {
+		0 => (Fraction::from(0), 1),
+		30 => (Fraction::new(1, 2), 1),
+		45 => (Fraction::new(1, 2), 2),
+		60 => (Fraction::new(1, 2), 3),
+		90 => (Fraction::from(1), 1),
+		_ => return None,
+	};
+	Some((coefficient * sign.into(), radicand))
+}
+
+// a\sqrt{r} / b\sqrt{s} = a/(bs) \sqrt{rs}
+fn divide(numerator: (Fraction, i32), denominator: (Fraction, i32)) -> Option<(Fraction, i32)> {
+	let (a, r) = numerator;
+	let (b, s) = denominator;
+	if b.is_zero() {
+		return None;
+	}
+	Some((a / (b * s.into()), r * s))
+}
+
+// k\pi, \frac{\pi}{n} or \frac{k\pi}{n} in degrees
+fn radians_to_degrees(exp: &Expression) -> Option<Fraction> {
+	match exp {
+		Expression::Variable(v) if v == "\\pi" => Some(180.into()),
+		Expression::Product(p) if p.factors.len() == 1 => {
+			Some(p.coefficient * radians_to_degrees(&p.factors[0])?)
+		}
+		Expression::Quotient(q) => match q.denominator.as_ref() {
+			Expression::Numeral(d) if d.is_nonzero() => Some(radians_to_degrees(&q.numerator)? / *d),
+			_ => None,
+		},
+		_ => None,
+	}
+}
@@ -1,3 +1,4 @@
+pub mod diff;
 pub mod exponent;
 pub mod function;
 pub mod numeral;
@@ -5,8 +6,12 @@ pub mod product;
 pub mod quotient;
 pub mod sum;
 pub mod variable;
+pub mod visit;
+pub use diff::{diff, Change, Diff, Difference, Step};
+pub use exponent::binomial::{binomial_coefficient, Binomial};
+pub use exponent::surd::{has_surd, is_surd, rationalising_factor, simplify_surd, surd_parts};
 pub use exponent::Exponent;
-pub use function::Fn;
+pub use function::{Abs, Fn, Log, Trig, TrigFunction, TrigIdentity, E};
 pub use numeral::fraction_gcd::{fraction_gcd, fraction_lcm};
 pub use numeral::Fraction;
 pub use product::product_lcm::{lcm_diff, product_lcm};
@@ -15,7 +20,8 @@ pub use quotient::Quotient;
 use std::collections::{HashMap, VecDeque};
 use std::convert::TryInto;
 use std::{cmp, fmt};
-pub use sum::Sum;
+pub use sum::{like_term_key, Sum};
+pub use visit::{Visitor, VisitorMut};
 
 #[macro_export]
 macro_rules! sum {
@@ -97,6 +103,16 @@ macro_rules! exp {
 	};
 }
 
+#[macro_export]
+macro_rules! sqrt {
+	( $x:expr ) => {
+		Expression::Exponent(Exponent {
+			base: Box::new($x.into()),
+			exponent: Box::new(Expression::Numeral(Fraction::new(1, 2))),
+		})
+	};
+}
+
 #[derive(Debug, Clone)]
 pub enum Expression {
 	Sum(Sum),
@@ -106,7 +122,6 @@ pub enum Expression {
 	Numeral(Fraction),
 	Variable(String),
 	Fn(Fn),
-	// TODO: unary functions
 }
 
 impl fmt::Display for Expression {
@@ -120,6 +135,9 @@ impl fmt::Display for Expression {
 			Expression::Numeral(n) => write!(formatter, "{}", n),
 			Expression::Fn(f) => match f {
 				Fn::Brackets(b) => write!(formatter, "{}", b),
+				Fn::Trig(t) => write!(formatter, "{}", t),
+				Fn::Log(l) => write!(formatter, "{}", l),
+				Fn::Abs(a) => write!(formatter, "{}", a),
 			},
 		}
 	}
@@ -140,6 +158,9 @@ impl SubIn for Expression {
 			Expression::Numeral(n) => n.sub_in(var, val),
 			Expression::Fn(f) => match f {
 				Fn::Brackets(b) => b.sub_in(var, val),
+				Fn::Trig(t) => t.sub_in(var, val),
+				Fn::Log(l) => l.sub_in(var, val),
+				Fn::Abs(a) => a.sub_in(var, val),
 			},
 		};
 		r.simplify();
@@ -149,62 +170,21 @@ impl SubIn for Expression {
 
 impl Expression {
 	pub fn remove_singletons(&mut self) -> () {
-		match self {
-			Expression::Sum(s) => {
-				for t in s.terms.iter_mut() {
-					t.remove_singletons();
-				}
-				if s.terms.len() == 0 {
-					*self = Expression::Numeral(0.into());
-				} else if s.terms.len() == 1 {
-					*self = s.terms[0].as_mut().clone();
-				}
-			}
-			Expression::Product(p) => {
-				for f in p.factors.iter_mut() {
-					f.remove_singletons();
-				}
-				if p.coefficient.is_zero() {
-					*self = Expression::Numeral(Fraction::from(0));
-					return;
-				}
-				if p.factors.len() == 0 {
-					*self = Expression::Numeral(p.coefficient);
-				} else if p.factors.len() == 1 && p.coefficient == 1.into() {
-					*self = p.factors[0].as_mut().clone();
-				}
-			}
-			Expression::Quotient(q) => {
-				q.numerator.remove_singletons();
-				q.denominator.remove_singletons();
-			}
-			Expression::Exponent(e) => {
-				e.base.remove_singletons();
-				e.exponent.remove_singletons();
-			}
-			_ => (),
-		}
+		self.walk_mut(&mut RemoveSingletons);
 	}
 
-	pub fn simplify(&mut self) -> () {
-		self.remove_brackets();
-		self.remove_singletons();
+	// the changes simplify makes to a node once its children are simplified
+	fn simplify_node(&mut self) {
 		match self {
-			Expression::Sum(s) => {
-				for t in s.terms.iter_mut() {
-					t.simplify();
-				}
-				s.simplify();
-			}
-			Expression::Product(p) => {
-				for f in p.factors.iter_mut() {
-					f.simplify();
-				}
-				p.simplify();
-			}
+			Expression::Sum(s) => s.simplify(),
+			Expression::Product(p) => p.simplify(),
 			Expression::Exponent(e) => {
-				e.base.simplify();
-				e.exponent.simplify();
+				// (x^a)^b -> x^{ab}
+				if let Some(power) = e.nested_power() {
+					*self = power;
+					self.simplify();
+					return;
+				}
 				// TODO: refactor
 				// number^number -> number
 				match (e.base.as_ref(), e.exponent.as_ref()) {
@@ -212,8 +192,14 @@ impl Expression {
 						if e.is_integer() {
 							let n = Box::new(Expression::Numeral(b.pow(e.numerator)));
 							*self = *n;
+						} else if let Some(surd) = simplify_surd(b, e) {
+							*self = surd;
 						}
 					}
+					// b^{\log_b x} -> x
+					(b, Expression::Fn(Fn::Log(l))) if b.to_string() == l.base.to_string() => {
+						*self = l.argument.as_ref().clone();
+					}
 					_ => {
 						if let Expression::Numeral(n) = e.exponent.as_ref() {
 							if n == &(1 as i32).into() {
@@ -270,6 +256,23 @@ impl Expression {
 					}
 				}
 			}
+			Expression::Fn(Fn::Trig(t)) => {
+				if let Some(value) = t.exact_value() {
+					*self = value;
+				}
+			}
+			Expression::Fn(Fn::Log(l)) => {
+				if let Some(value) = l.exact_value() {
+					*self = value;
+					self.simplify();
+				}
+			}
+			Expression::Fn(Fn::Abs(a)) => {
+				if let Some(value) = a.simplified() {
+					*self = value;
+					self.simplify();
+				}
+			}
 			// variable, numeral
 			Expression::Quotient(q) => {
 				q.simplify();
@@ -292,12 +295,18 @@ impl Expression {
 		self.remove_singletons();
 	}
 
+	pub fn simplify(&mut self) -> () {
+		self.walk_mut(&mut Simplify);
+	}
+
 	pub fn expand(&mut self) -> () {
+		self.walk_mut(&mut Expand);
+	}
+
+	// multiplies out the sums in a node once its children are expanded
+	fn expand_node(&mut self) {
 		match self {
 			Expression::Product(p) => {
-				for f in p.factors.iter_mut() {
-					f.expand();
-				}
 				// find sums
 				let mut sums: VecDeque<Sum> = VecDeque::new();
 				let mut others: Vec<Box<Expression>> = Vec::new();
@@ -336,18 +345,7 @@ impl Expression {
 					*self = Expression::Sum(s);
 				}
 			}
-			Expression::Sum(s) => {
-				for t in s.terms.iter_mut() {
-					t.expand();
-				}
-			}
-			Expression::Quotient(q) => {
-				q.numerator.expand();
-				q.denominator.expand();
-			}
 			Expression::Exponent(e) => {
-				e.base.expand();
-				e.exponent.expand();
 				if let Expression::Numeral(n) = e.exponent.as_ref() {
 					if n.is_integer() && n.is_positive() {
 						if let Expression::Product(p) = e.base.as_ref() {
@@ -387,25 +385,7 @@ impl Expression {
 	}
 
 	pub fn remove_nested_sums(&mut self) -> () {
-		match self {
-			Expression::Sum(s) => {
-				s.remove_nested_sums();
-			}
-			Expression::Product(p) => {
-				for f in p.factors.iter_mut() {
-					f.remove_nested_sums();
-				}
-			}
-			Expression::Quotient(q) => {
-				q.numerator.remove_nested_sums();
-				q.denominator.remove_nested_sums();
-			}
-			Expression::Exponent(e) => {
-				e.base.remove_nested_sums();
-				e.exponent.remove_nested_sums();
-			}
-			_ => (),
-		}
+		self.walk_mut(&mut RemoveNestedSums);
 	}
 
 	// take out common numeric/variable/exponent factors
@@ -845,6 +825,86 @@ impl Expression {
 		}
 	}
 
+	// multiplies numerator and denominator by
+	// the conjugate for binomial surd denominators a + b\sqrt{c}
+	// the surd for monomial denominators b\sqrt[n]{c}
+	// binomial denominators with higher roots, such as 1 + \sqrt[3]{2}, are left unchanged
+	// only works if outer-most expression type is a quotient
+	pub fn rationalise_denominator(&mut self) {
+		if let Expression::Quotient(q) = self {
+			let multiplier = match q.denominator.as_ref() {
+				Expression::Sum(s) if s.terms.len() == 2 => {
+					if !s.terms.iter().any(|t| has_surd(t)) {
+						return;
+					}
+					// the conjugate only rationalises square roots
+					let index = |t: &Expression| match t {
+						Expression::Product(p) => p.factors.iter().find_map(|f| surd_parts(f)),
+						_ => surd_parts(t),
+					};
+					if s
+						.terms
+						.iter()
+						.any(|t| index(t).is_some_and(|(_, n)| n != 2))
+					{
+						return;
+					}
+					Expression::Sum(Sum {
+						terms: vec![
+							s.terms[0].clone(),
+							Box::new(Expression::Product(Product {
+								coefficient: Fraction::from(-1),
+								factors: vec![s.terms[1].clone()],
+							})),
+						],
+					})
+				}
+				_ => match rationalising_factor(&q.denominator) {
+					Some(f) => f,
+					None => return,
+				},
+			};
+			let mut numerator = Expression::Product(Product {
+				coefficient: Fraction::from(1),
+				factors: vec![q.numerator.clone(), Box::new(multiplier.clone())],
+			});
+			numerator.expand_and_simplify();
+			let mut denominator = Expression::Product(Product {
+				coefficient: Fraction::from(1),
+				factors: vec![q.denominator.clone(), Box::new(multiplier)],
+			});
+			denominator.expand_and_simplify();
+			// cancel common factors with a numeral denominator, keeping it positive
+			if let Expression::Numeral(d) = denominator {
+				let mut factor = if d.is_negative() { d.negative() } else { d };
+				if let Expression::Sum(s) = &numerator {
+					for t in s.terms.iter() {
+						factor = match t.as_ref() {
+							Expression::Numeral(n) => fraction_gcd(&factor, n),
+							Expression::Product(p) => fraction_gcd(&factor, &p.coefficient),
+							_ => fraction_gcd(&factor, &Fraction::from(1)),
+						};
+					}
+				}
+				if d.is_negative() {
+					factor = factor.negative();
+				}
+				denominator = Expression::Numeral(d / factor);
+				numerator = Expression::Product(Product {
+					coefficient: factor.reciprocal(),
+					factors: vec![Box::new(numerator)],
+				});
+				numerator.expand_and_simplify();
+			}
+			let mut exp = Expression::Quotient(Quotient {
+				numerator: Box::new(numerator),
+				denominator: Box::new(denominator),
+			});
+			exp.simplify();
+			*self = exp;
+		}
+	}
+
 	// combine sums of quotients
 	// only work for numbers at the moment
 	pub fn combine_fraction(&mut self) -> () {
@@ -949,34 +1009,159 @@ impl Expression {
 		}
 	}
 
-	pub fn remove_brackets(&mut self) -> () {
+	// whether the variable appears anywhere in the expression tree
+	pub fn contains_variable(&self, x: &str) -> bool {
 		match self {
+			Expression::Sum(s) => s.terms.iter().any(|t| t.contains_variable(x)),
+			Expression::Product(p) => p.factors.iter().any(|f| f.contains_variable(x)),
+			Expression::Quotient(q) => {
+				q.numerator.contains_variable(x) || q.denominator.contains_variable(x)
+			}
+			Expression::Exponent(e) => e.base.contains_variable(x) || e.exponent.contains_variable(x),
+			Expression::Variable(v) => v == x,
+			Expression::Numeral(_) => false,
 			Expression::Fn(f) => match f {
-				Fn::Brackets(b) => {
-					*self = b.expression.as_mut().clone();
+				Fn::Brackets(b) => b.expression.contains_variable(x),
+				Fn::Trig(t) => t.argument.contains_variable(x),
+				Fn::Log(l) => l.base.contains_variable(x) || l.argument.contains_variable(x),
+				Fn::Abs(a) => a.argument.contains_variable(x),
+			},
+		}
+	}
+
+	// variables in the expression, sorted and without repeats
+	// the constants \mathrm{e} and \pi are left out
+	pub fn variables(&self) -> Vec<String> {
+		let mut variables: Vec<String> = Vec::new();
+		self.collect_variables(&mut variables);
+		variables.sort();
+		variables.dedup();
+		variables
+	}
+
+	fn collect_variables(&self, variables: &mut Vec<String>) {
+		match self {
+			Expression::Sum(s) => s.terms.iter().for_each(|t| t.collect_variables(variables)),
+			Expression::Product(p) => p
+				.factors
+				.iter()
+				.for_each(|f| f.collect_variables(variables)),
+			Expression::Quotient(q) => {
+				q.numerator.collect_variables(variables);
+				q.denominator.collect_variables(variables);
+			}
+			Expression::Exponent(e) => {
+				e.base.collect_variables(variables);
+				e.exponent.collect_variables(variables);
+			}
+			Expression::Variable(v) if v != E && v != "\\pi" => variables.push(v.clone()),
+			Expression::Variable(_) | Expression::Numeral(_) => (),
+			Expression::Fn(f) => match f {
+				Fn::Brackets(b) => b.expression.collect_variables(variables),
+				Fn::Trig(t) => t.argument.collect_variables(variables),
+				Fn::Log(l) => {
+					l.base.collect_variables(variables);
+					l.argument.collect_variables(variables);
 				}
+				Fn::Abs(a) => a.argument.collect_variables(variables),
 			},
+		}
+	}
+
+	pub fn remove_brackets(&mut self) -> () {
+		self.walk_mut(&mut RemoveBrackets);
+	}
+}
+
+// the passes above as traversals
+// each keeps to the nodes the pass has always visited, so most skip function arguments
+
+struct RemoveSingletons;
+
+impl VisitorMut for RemoveSingletons {
+	fn leave_mut(&mut self, exp: &mut Expression) {
+		match exp {
 			Expression::Sum(s) => {
-				for t in s.terms.iter_mut() {
-					t.remove_brackets();
+				if s.terms.is_empty() {
+					*exp = Expression::Numeral(0.into());
+				} else if s.terms.len() == 1 {
+					*exp = s.terms[0].as_mut().clone();
 				}
 			}
 			Expression::Product(p) => {
-				for f in p.factors.iter_mut() {
-					f.remove_brackets();
+				if p.coefficient.is_zero() {
+					*exp = Expression::Numeral(Fraction::from(0));
+				} else if p.factors.is_empty() {
+					*exp = Expression::Numeral(p.coefficient);
+				} else if p.factors.len() == 1 && p.coefficient == 1.into() {
+					*exp = p.factors[0].as_mut().clone();
 				}
 			}
-			Expression::Exponent(e) => {
-				e.base.remove_brackets();
-				e.exponent.remove_brackets();
-			}
-			Expression::Quotient(q) => {
-				q.numerator.remove_brackets();
-				q.denominator.remove_brackets();
-			}
 			_ => (),
 		}
 	}
+
+	fn visit_functions(&self) -> bool {
+		false
+	}
+}
+
+// brackets are replaced without looking inside them
+struct RemoveBrackets;
+
+impl VisitorMut for RemoveBrackets {
+	fn visit_mut(&mut self, exp: &mut Expression) -> bool {
+		if let Expression::Fn(Fn::Brackets(b)) = exp {
+			*exp = b.expression.as_mut().clone();
+			return false;
+		}
+		true
+	}
+}
+
+// nested sums are flattened one level
+struct RemoveNestedSums;
+
+impl VisitorMut for RemoveNestedSums {
+	fn visit_mut(&mut self, exp: &mut Expression) -> bool {
+		if let Expression::Sum(s) = exp {
+			s.remove_nested_sums();
+			return false;
+		}
+		true
+	}
+
+	fn visit_functions(&self) -> bool {
+		false
+	}
+}
+
+struct Expand;
+
+impl VisitorMut for Expand {
+	fn leave_mut(&mut self, exp: &mut Expression) {
+		exp.expand_node();
+	}
+
+	fn visit_functions(&self) -> bool {
+		false
+	}
+}
+
+// each node is tidied before its children are simplified
+// quotients simplify their own numerator and denominator
+struct Simplify;
+
+impl VisitorMut for Simplify {
+	fn visit_mut(&mut self, exp: &mut Expression) -> bool {
+		exp.remove_brackets();
+		exp.remove_singletons();
+		!matches!(exp, Expression::Quotient(_))
+	}
+
+	fn leave_mut(&mut self, exp: &mut Expression) {
+		exp.simplify_node();
+	}
 }
 
 impl TryInto<Fraction> for Expression {
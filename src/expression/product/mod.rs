@@ -1,4 +1,3 @@
-use crate::expression::function::Fn;
 use crate::expression::numeral::Fraction;
 use crate::expression::{Expression, SubIn};
 use std::collections::HashMap;
@@ -151,6 +150,8 @@ impl Product {
 		let mut i = 0;
 		// variable string: (coefficient, term index)
 		let mut term_map: HashMap<String, (Fraction, usize)> = std::collections::HashMap::new();
+		// root index: (product of radicands, term index)
+		let mut surd_map: HashMap<u32, (i32, usize)> = HashMap::new();
 		for factor in self.factors.iter_mut() {
 			match factor.as_mut() {
 				Expression::Numeral(n) => {
@@ -167,7 +168,19 @@ impl Product {
 					}
 				}
 				Expression::Exponent(e) => {
-					if let (Expression::Variable(v), Expression::Numeral(n)) =
+					if let Some((radicand, index)) = e.surd_parts() {
+						// surds with the same index multiply under the root
+						if let Some((r, _)) = surd_map.get_mut(&index) {
+							if let Some(product) = r.checked_mul(radicand) {
+								*r = product;
+								continue;
+							}
+						} else {
+							surd_map.insert(index, (radicand, i));
+						}
+						factors.push(factor.clone());
+						i += 1;
+					} else if let (Expression::Variable(v), Expression::Numeral(n)) =
 						(e.base.as_ref(), e.exponent.as_ref())
 					{
 						if term_map.contains_key(v) {
@@ -189,6 +202,13 @@ impl Product {
 				}
 			}
 		}
+		// combined surds are simplified with the rest of the factors
+		for (index, (radicand, i)) in surd_map.iter() {
+			*factors[*i] = Expression::Exponent(Exponent {
+				base: Box::new(Expression::Numeral(Fraction::from(*radicand))),
+				exponent: Box::new(Expression::Numeral(Fraction::new(1, *index as i32))),
+			});
+		}
 		// mutate final factor
 		let mut offset = 0;
 		for (var, (power, index)) in term_map.iter() {
@@ -243,6 +263,15 @@ impl Product {
 		for factor in self.factors.iter_mut() {
 			factor.simplify();
 		}
+		// simplifying factors may produce numerals, e.g. \sqrt{9} -> 3
+		if self
+			.factors
+			.iter()
+			.any(|f| matches!(f.as_ref(), Expression::Numeral(_)))
+		{
+			self.collect_coefficients();
+		}
+		self.collect_symbolic_powers();
 		self.remove_nested_products();
 	}
 
@@ -294,11 +323,9 @@ impl Product {
 				Expression::Product(p) => {
 					products.push(p.coefficient.to_string() + &p.lexical_string());
 				}
-				Expression::Fn(f) => match f {
-					Fn::Brackets(b) => {
-						others.push(b.to_string());
-					}
-				},
+				Expression::Fn(_) => {
+					others.push(factor.to_string());
+				}
 			}
 		}
 		sums.sort();
@@ -307,6 +334,7 @@ impl Product {
 		vars.sort();
 		numerals.sort();
 		products.sort();
+		others.sort();
 		let mut terms: Vec<String> = Vec::new();
 		terms.extend(sums);
 		terms.extend(exps);
@@ -314,6 +342,7 @@ impl Product {
 		terms.extend(vars);
 		terms.extend(numerals);
 		terms.extend(products);
+		terms.extend(others);
 		terms.join("")
 	}
 
@@ -494,5 +494,6 @@ impl Quotient {
 		}
 		self.numerator.simplify();
 		self.denominator.simplify();
+		self.cancel_symbolic_powers();
 	}
 }
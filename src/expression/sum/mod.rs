@@ -1,4 +1,3 @@
-use crate::expression::function::Fn;
 use crate::expression::numeral::Fraction;
 use crate::expression::{Expression, Product, SubIn};
 use crate::prod;
@@ -236,6 +235,17 @@ impl Sum {
 		}
 	}
 
+	// indices of the first pair of terms that combine_like_terms would combine
+	pub fn like_terms(&self) -> Option<(usize, usize)> {
+		let keys: Vec<String> = self.terms.iter().map(|t| like_term_key(t)).collect();
+		for (j, key) in keys.iter().enumerate() {
+			if let Some(i) = keys[..j].iter().position(|k| k == key) {
+				return Some((i, j));
+			}
+		}
+		None
+	}
+
 	pub fn simplify(&mut self) -> () {
 		self.remove_zeros();
 		self.remove_nested_sums();
@@ -273,11 +283,9 @@ impl Sum {
 				Expression::Product(p) => {
 					products.push(p.coefficient.to_string() + &p.lexical_string());
 				}
-				Expression::Fn(f) => match f {
-					Fn::Brackets(b) => {
-						others.push(b.to_string());
-					}
-				},
+				Expression::Fn(_) => {
+					others.push(term.to_string());
+				}
 			}
 		}
 		sums.sort();
@@ -286,6 +294,7 @@ impl Sum {
 		vars.sort();
 		numerals.sort();
 		products.sort();
+		others.sort();
 		let mut terms: Vec<String> = Vec::new();
 		terms.extend(sums);
 		terms.extend(exps);
@@ -293,10 +302,21 @@ impl Sum {
 		terms.extend(vars);
 		terms.extend(numerals);
 		terms.extend(products);
+		terms.extend(others);
 		terms.join("+")
 	}
 }
 
+// the key used by combine_like_terms: the factors of a product without its coefficient
+pub fn like_term_key(term: &Expression) -> String {
+	match term {
+		Expression::Product(p) => p.lexical_string(),
+		Expression::Numeral(_) => "numeral".to_string(),
+		Expression::Sum(s) => s.lexical_string(),
+		_ => term.to_string(),
+	}
+}
+
 fn handle_number(
 	mut first_number: Option<(usize, Fraction)>,
 	mut other_indices: Vec<usize>,
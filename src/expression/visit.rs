@@ -0,0 +1,187 @@
+use crate::expression::{Expression, Fn};
+
+#[cfg(test)]
+mod tests {
+	use crate::expression::*;
+	use crate::*;
+
+	struct NodeCounter {
+		count: usize,
+	}
+
+	impl Visitor for NodeCounter {
+		fn visit(&mut self, _exp: &Expression) -> bool {
+			self.count += 1;
+			true
+		}
+	}
+
+	struct Rename;
+
+	impl VisitorMut for Rename {
+		fn visit_mut(&mut self, exp: &mut Expression) -> bool {
+			if let Expression::Variable(v) = exp {
+				if v == "x" {
+					*v = "t".to_string();
+				}
+			}
+			true
+		}
+	}
+
+	#[test]
+	fn visitor() {
+		// 2x + \sin y: sum, product, x, sin, y
+		let exp = sum!(prod!(2, "x"), sin!("y"));
+		let mut counter = NodeCounter { count: 0 };
+		exp.walk(&mut counter);
+		assert_eq!(counter.count, 5);
+		let mut exp = sum!(exp!("x", 2), ln!("x"));
+		exp.walk_mut(&mut Rename);
+		assert_eq!(exp.to_string(), "t^2 + \\ln t");
+	}
+
+	#[test]
+	fn fold_and_map() {
+		let exp = sum!(prod!(3, "x", "y"), quotient!("z", sum!("x", 1)));
+		let variables = exp.fold(Vec::new(), |mut v, e| {
+			if let Expression::Variable(name) = e {
+				v.push(name.clone());
+			}
+			v
+		});
+		assert_eq!(variables, vec!["x", "y", "z", "x"]);
+		let edges = exp.fold(0, |n, e| n + e.children().len());
+		assert_eq!(edges, 8);
+		// the coefficient 3 is part of the product, so only the 1 is doubled
+		let mut doubled = exp.map(|e| match e {
+			Expression::Numeral(n) => Expression::Numeral(n * 2.into()),
+			_ => e,
+		});
+		assert_eq!(doubled.to_string(), "3xy + \\frac{z}{x + 2}");
+		doubled.simplify();
+		assert_eq!(doubled.to_string(), "3xy + \\frac{z}{x + 2}");
+	}
+}
+
+// a read-only traversal of an expression tree
+// visit is called before the children of a node and leave after them
+// the coefficient of a product is part of the product node, not a child
+pub trait Visitor {
+	// return false to skip the children of this node
+	fn visit(&mut self, _exp: &Expression) -> bool {
+		true
+	}
+
+	fn leave(&mut self, _exp: &Expression) {}
+}
+
+// a traversal that may change the tree
+// children are those of the node after visit_mut has changed it
+pub trait VisitorMut {
+	// return false to skip the children of this node
+	fn visit_mut(&mut self, _exp: &mut Expression) -> bool {
+		true
+	}
+
+	fn leave_mut(&mut self, _exp: &mut Expression) {}
+
+	// whether to go into the arguments of functions
+	fn visit_functions(&self) -> bool {
+		true
+	}
+}
+
+impl Expression {
+	// the direct subexpressions, including function arguments
+	pub fn children(&self) -> Vec<&Expression> {
+		match self {
+			Expression::Sum(s) => s.terms.iter().map(|t| t.as_ref()).collect(),
+			Expression::Product(p) => p.factors.iter().map(|f| f.as_ref()).collect(),
+			Expression::Quotient(q) => vec![q.numerator.as_ref(), q.denominator.as_ref()],
+			Expression::Exponent(e) => vec![e.base.as_ref(), e.exponent.as_ref()],
+			Expression::Variable(_) | Expression::Numeral(_) => Vec::new(),
+			Expression::Fn(f) => match f {
+				Fn::Brackets(b) => vec![b.expression.as_ref()],
+				Fn::Trig(t) => vec![t.argument.as_ref()],
+				Fn::Log(l) => vec![l.base.as_ref(), l.argument.as_ref()],
+				Fn::Abs(a) => vec![a.argument.as_ref()],
+			},
+		}
+	}
+
+	pub fn children_mut(&mut self) -> Vec<&mut Expression> {
+		match self {
+			Expression::Sum(s) => s.terms.iter_mut().map(|t| t.as_mut()).collect(),
+			Expression::Product(p) => p.factors.iter_mut().map(|f| f.as_mut()).collect(),
+			Expression::Quotient(q) => vec![q.numerator.as_mut(), q.denominator.as_mut()],
+			Expression::Exponent(e) => vec![e.base.as_mut(), e.exponent.as_mut()],
+			Expression::Variable(_) | Expression::Numeral(_) => Vec::new(),
+			Expression::Fn(f) => match f {
+				Fn::Brackets(b) => vec![b.expression.as_mut()],
+				Fn::Trig(t) => vec![t.argument.as_mut()],
+				Fn::Log(l) => vec![l.base.as_mut(), l.argument.as_mut()],
+				Fn::Abs(a) => vec![a.argument.as_mut()],
+			},
+		}
+	}
+
+	pub fn walk<V: Visitor + ?Sized>(&self, visitor: &mut V) {
+		if visitor.visit(self) {
+			for child in self.children() {
+				child.walk(visitor);
+			}
+		}
+		visitor.leave(self);
+	}
+
+	pub fn walk_mut<V: VisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+		if visitor.visit_mut(self) && (visitor.visit_functions() || !matches!(self, Expression::Fn(_)))
+		{
+			for child in self.children_mut() {
+				child.walk_mut(visitor);
+			}
+		}
+		visitor.leave_mut(self);
+	}
+
+	// combines every node, parents before children
+	pub fn fold<T, F: FnMut(T, &Expression) -> T>(&self, init: T, f: F) -> T {
+		struct Fold<T, F> {
+			value: Option<T>,
+			f: F,
+		}
+		impl<T, F: FnMut(T, &Expression) -> T> Visitor for Fold<T, F> {
+			fn visit(&mut self, exp: &Expression) -> bool {
+				if let Some(value) = self.value.take() {
+					self.value = Some((self.f)(value, exp));
+				}
+				true
+			}
+		}
+		let mut fold = Fold {
+			value: Some(init),
+			f,
+		};
+		self.walk(&mut fold);
+		fold.value.unwrap()
+	}
+
+	// rebuilds the tree, replacing children before their parents
+	// product coefficients are not passed to f, only the product as a whole
+	// the result is not simplified
+	pub fn map<F: FnMut(Expression) -> Expression>(&self, f: F) -> Expression {
+		struct Map<F> {
+			f: F,
+		}
+		impl<F: FnMut(Expression) -> Expression> VisitorMut for Map<F> {
+			fn leave_mut(&mut self, exp: &mut Expression) {
+				let node = std::mem::replace(exp, Expression::Numeral(0.into()));
+				*exp = (self.f)(node);
+			}
+		}
+		let mut exp = self.clone();
+		exp.walk_mut(&mut Map { f });
+		exp
+	}
+}
@@ -0,0 +1,262 @@
+use crate::expression::{Exponent, Expression, Fraction, Product, Quotient, Sum};
+use crate::marking::{check_equivalence, Equivalence};
+use std::fmt;
+
+#[cfg(test)]
+mod tests {
+	use crate::expression::*;
+	use crate::generate::*;
+	use crate::*;
+
+	fn show(question: &Expression, answer: &Expression) -> Vec<String> {
+		distractors(question, answer)
+			.iter()
+			.map(|(m, d)| format!("{:?}: {}", m, d))
+			.collect()
+	}
+
+	#[test]
+	fn expansion() {
+		// 3x - 2(x + 4)
+		let question = Expression::Sum(Sum {
+			terms: vec![Box::new(prod!(3, "x")), Box::new(prod!(-2, sum!("x", 4)))],
+		});
+		let mut answer = question.clone();
+		answer.expand_and_simplify();
+		assert_eq!(answer.to_string(), "x - 8");
+		assert_eq!(
+			show(&question, &answer),
+			vec!["SignOfBracket: x + 8", "CoefficientOfBracket: x + 4"]
+		);
+		let question = exp!(sum!("x", 3), 2);
+		let mut answer = question.clone();
+		answer.expand_and_simplify();
+		assert_eq!(show(&question, &answer), vec!["MissingMiddleTerm: x^2 + 9"]);
+	}
+
+	#[test]
+	fn fractions() {
+		let question = Expression::Sum(Sum {
+			terms: vec![
+				Box::new(quotient!("x", 2)),
+				Box::new(quotient!(prod!(2, "x"), 3)),
+			],
+		});
+		let mut answer = question.clone();
+		answer.combine_fraction();
+		assert_eq!(
+			show(&question, &answer),
+			vec!["AddDenominators: \\frac{3x}{5}"]
+		);
+		// the denominators add to 0
+		let question = Expression::Sum(Sum {
+			terms: vec![
+				Box::new(quotient!(1, "x")),
+				Box::new(quotient!(1, prod!(-1, "x"))),
+			],
+		});
+		assert!(show(&question, &0.into()).is_empty());
+		let question = Expression::Sum(Sum {
+			terms: vec![
+				Box::new(quotient!(1, sum!("x", -1))),
+				Box::new(quotient!(1, sum!(1, prod!(-1, "x")))),
+			],
+		});
+		assert!(show(&question, &0.into()).is_empty());
+	}
+
+	#[test]
+	fn distinct_from_answer() {
+		// the sign error makes no difference when the other terms are 0
+		let question = Expression::Product(Product {
+			coefficient: (-1).into(),
+			factors: vec![Box::new(Expression::Sum(Sum {
+				terms: vec![Box::new("x".into()), Box::new(0.into())],
+			}))],
+		});
+		let answer = prod!(-1, "x");
+		assert!(distractors(&question, &answer).is_empty());
+	}
+}
+
+// common mistakes made when expanding, squaring and adding fractions
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Misconception {
+	// -(a + b) = -a + b
+	SignOfBracket,
+	// k(a + b) = ka + b
+	CoefficientOfBracket,
+	// (a + b)^2 = a^2 + b^2
+	MissingMiddleTerm,
+	// \frac{a}{b} + \frac{c}{d} = \frac{a + c}{b + d}
+	AddDenominators,
+}
+
+impl fmt::Display for Misconception {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Misconception::SignOfBracket => {
+				write!(f, "sign only applied to the first term in the bracket")
+			}
+			Misconception::CoefficientOfBracket => {
+				write!(
+					f,
+					"coefficient only multiplied with the first term in the bracket"
+				)
+			}
+			Misconception::MissingMiddleTerm => write!(f, "missing middle term when squaring a bracket"),
+			Misconception::AddDenominators => write!(f, "numerators and denominators added"),
+		}
+	}
+}
+
+const MISCONCEPTIONS: [Misconception; 4] = [
+	Misconception::SignOfBracket,
+	Misconception::CoefficientOfBracket,
+	Misconception::MissingMiddleTerm,
+	Misconception::AddDenominators,
+];
+
+// wrong answers from each misconception that applies to the question
+// each distractor is checked to be different from the answer and from the other distractors
+pub fn distractors(question: &Expression, answer: &Expression) -> Vec<(Misconception, Expression)> {
+	let mut result: Vec<(Misconception, Expression)> = Vec::new();
+	for misconception in MISCONCEPTIONS {
+		let mut wrong = question.clone();
+		if !misapply(&mut wrong, misconception) {
+			continue;
+		}
+		if misconception == Misconception::AddDenominators {
+			wrong.simplify();
+		} else {
+			wrong.expand_and_simplify();
+			wrong.combine_fraction();
+		}
+		let is_distinct =
+			|other: &Expression| check_equivalence(other, &wrong) == Equivalence::NotEquivalent;
+		if is_distinct(answer) && result.iter().all(|(_, d)| is_distinct(d)) {
+			result.push((misconception, wrong));
+		}
+	}
+	result
+}
+
+// applies the misconception to every subexpression where it fits
+// returns false if it does not apply anywhere
+fn misapply(exp: &mut Expression, misconception: Misconception) -> bool {
+	let mut applied = false;
+	match exp {
+		Expression::Sum(s) => {
+			for t in s.terms.iter_mut() {
+				applied |= misapply(t, misconception);
+			}
+		}
+		Expression::Product(p) => {
+			for f in p.factors.iter_mut() {
+				applied |= misapply(f, misconception);
+			}
+		}
+		Expression::Quotient(q) => {
+			applied |= misapply(&mut q.numerator, misconception);
+			applied |= misapply(&mut q.denominator, misconception);
+		}
+		Expression::Exponent(e) => applied |= misapply(&mut e.base, misconception),
+		_ => (),
+	}
+	let wrong = match (misconception, &*exp) {
+		(Misconception::SignOfBracket, Expression::Product(p)) if p.coefficient.is_negative() => {
+			bracket(p).map(|s| first_term_only(s, p.coefficient, p.coefficient.abs()))
+		}
+		(Misconception::CoefficientOfBracket, Expression::Product(p))
+			if !p.coefficient.abs().is_one() =>
+		{
+			bracket(p).map(|s| first_term_only(s, p.coefficient, 1.into()))
+		}
+		(Misconception::MissingMiddleTerm, Expression::Exponent(e)) => {
+			match (e.base.as_ref(), e.exponent.as_ref()) {
+				(Expression::Sum(s), Expression::Numeral(n))
+					if s.terms.len() > 1 && n.is_integer() && n.numerator > 1 =>
+				{
+					Some(Expression::Sum(Sum {
+						terms: s
+							.terms
+							.iter()
+							.map(|t| {
+								Box::new(Expression::Exponent(Exponent {
+									base: t.clone(),
+									exponent: e.exponent.clone(),
+								}))
+							})
+							.collect(),
+					}))
+				}
+				_ => None,
+			}
+		}
+		(Misconception::AddDenominators, Expression::Sum(s)) => add_denominators(s),
+		_ => None,
+	};
+	if let Some(wrong) = wrong {
+		*exp = wrong;
+		applied = true;
+	}
+	applied
+}
+
+// the sum in k(a + b + ...)
+fn bracket(p: &Product) -> Option<&Sum> {
+	match p.factors.as_slice() {
+		[f] => match f.as_ref() {
+			Expression::Sum(s) if s.terms.len() > 1 => Some(s),
+			_ => None,
+		},
+		_ => None,
+	}
+}
+
+// ka + jb + jc + ...
+fn first_term_only(s: &Sum, k: Fraction, j: Fraction) -> Expression {
+	Expression::Sum(Sum {
+		terms: s
+			.terms
+			.iter()
+			.enumerate()
+			.map(|(i, t)| {
+				Box::new(Expression::Product(Product {
+					coefficient: if i == 0 { k } else { j },
+					factors: vec![t.clone()],
+				}))
+			})
+			.collect(),
+	})
+}
+
+// \frac{a + c}{b + d} from a sum of fractions
+fn add_denominators(s: &Sum) -> Option<Expression> {
+	let mut numerators: Vec<Box<Expression>> = Vec::new();
+	let mut denominators: Vec<Box<Expression>> = Vec::new();
+	for t in s.terms.iter() {
+		match t.as_ref() {
+			Expression::Quotient(q) => {
+				numerators.push(q.numerator.clone());
+				denominators.push(q.denominator.clone());
+			}
+			_ => return None,
+		}
+	}
+	if numerators.len() < 2 {
+		return None;
+	}
+	// the denominators can cancel, as in \frac{1}{x} + \frac{1}{-x}
+	let mut denominator = Expression::Sum(Sum {
+		terms: denominators,
+	});
+	denominator.expand_and_simplify();
+	if matches!(&denominator, Expression::Numeral(n) if n.is_zero()) {
+		return None;
+	}
+	Some(Expression::Quotient(Quotient {
+		numerator: Box::new(Expression::Sum(Sum { terms: numerators })),
+		denominator: Box::new(denominator),
+	}))
+}
@@ -0,0 +1,371 @@
+use crate::expression::{fraction_gcd, Exponent, Expression, Fraction, Product, Quotient, Sum};
+use std::fmt;
+pub mod distractor;
+pub mod reverse;
+pub mod rng;
+pub use distractor::{distractors, Misconception};
+pub use reverse::{factorise_from_factors, linear_equation_with_solution, quadratic_with_roots};
+pub use rng::Rng;
+
+#[cfg(test)]
+mod tests {
+	use crate::equation::linear_coefficients;
+	use crate::generate::*;
+
+	#[test]
+	fn reproducible() {
+		let constraints = Constraints::default();
+		for topic in [
+			Topic::Expand,
+			Topic::Factorise,
+			Topic::Simplify,
+			Topic::CombineFractions,
+		] {
+			let a = generate(&topic, &constraints, &mut Rng::new(2024)).unwrap();
+			let b = generate(&topic, &constraints, &mut Rng::new(2024)).unwrap();
+			assert_eq!(a.question.to_string(), b.question.to_string());
+			assert_eq!(a.answer.to_string(), b.answer.to_string());
+		}
+		let questions: Vec<String> = generate_many(&Topic::Expand, &constraints, 1, 3)
+			.unwrap()
+			.iter()
+			.map(|q| format!("{} = {}", q.question, q.answer))
+			.collect();
+		assert_eq!(
+			questions,
+			vec![
+				"\\left( - x + 5 \\right)\\left( 4x + 5 \\right) = - 4x^2 + 15x + 25",
+				"\\left( 4x - 7 \\right)\\left( - 9x - 1 \\right) = - 36x^2 + 59x + 7",
+				"\\left( 6x + 6 \\right)\\left( - 5x - 6 \\right) = - 30x^2 - 66x - 36"
+			]
+		);
+	}
+
+	#[test]
+	fn answers() {
+		let constraints = Constraints {
+			coefficients: (-5, 5),
+			..Constraints::default()
+		};
+		let mut rng = Rng::new(99);
+		for _ in 0..20 {
+			let q = generate(&Topic::Factorise, &constraints, &mut rng).unwrap();
+			let mut expanded = q.answer.clone();
+			expanded.expand_and_simplify();
+			assert_eq!(expanded.to_string(), q.question.to_string());
+			assert!(matches!(q.answer, Expression::Product(_)));
+		}
+		let q = generate(&Topic::Factorise, &constraints, &mut Rng::new(5)).unwrap();
+		assert_eq!(
+			format!("{} = {}", q.question, q.answer),
+			"10x^2 - 7x + 1 = \\left( 2x - 1 \\right)\\left( 5x - 1 \\right)"
+		);
+		let q = generate(&Topic::Simplify, &constraints, &mut Rng::new(5)).unwrap();
+		assert_eq!(
+			format!("{} = {}", q.question, q.answer),
+			"- x^2 - 5x^2 - 4x = - 6x^2 - 4x"
+		);
+		let constraints = Constraints {
+			terms: 3,
+			..constraints
+		};
+		let q = generate(&Topic::CombineFractions, &constraints, &mut Rng::new(5)).unwrap();
+		assert_eq!(
+			format!("{} = {}", q.question, q.answer),
+			"\\frac{- 4x - 1}{5} + \\frac{- 5x - 1}{2} + \\frac{3x + 2}{2} = \\frac{- 18x + 3}{10}"
+		);
+		let q = generate(&Topic::Expand, &constraints, &mut Rng::new(5)).unwrap();
+		assert_eq!(format!("{} = {}", q.question, q.answer), "\\left( - 4x^2 - x - 5 \\right)\\left( - x^2 - 4x + 3 \\right) = 4x^4 + 17x^3 - 3x^2 + 17x - 15");
+		// factors keep to the coefficient range
+		let constraints = Constraints {
+			coefficients: (3, 9),
+			..Constraints::default()
+		};
+		for seed in 0..20 {
+			let q = generate(&Topic::Factorise, &constraints, &mut Rng::new(seed)).unwrap();
+			let Expression::Product(p) = &q.answer else {
+				panic!("{} is not a product", q.answer);
+			};
+			for f in p.factors.iter() {
+				let (coefficients, constant) = linear_coefficients(f).unwrap();
+				assert!((3..=9).contains(&constant.numerator));
+				assert!((3..=9).contains(&coefficients["x"].numerator));
+			}
+		}
+		// every simplify question has like terms to collect
+		for seed in 0..50 {
+			let q = generate(
+				&Topic::Simplify,
+				&Constraints::default(),
+				&mut Rng::new(seed),
+			)
+			.unwrap();
+			assert_ne!(q.question.to_string(), q.answer.to_string());
+		}
+		// no nonzero coefficients to choose from
+		let constraints = Constraints {
+			coefficients: (0, 0),
+			..Constraints::default()
+		};
+		assert!(generate(&Topic::Simplify, &constraints, &mut Rng::new(5)).is_none());
+	}
+
+	#[test]
+	fn common_factor() {
+		let constraints = Constraints {
+			factors_nicely: false,
+			terms: 3,
+			..Constraints::default()
+		};
+		let q = generate(&Topic::Factorise, &constraints, &mut Rng::new(3)).unwrap();
+		assert_eq!(
+			format!("{} = {}", q.question, q.answer),
+			"- 42x^3 + 18x^2 - 42x = 6x\\left( - 7x^2 + 3x - 7 \\right)"
+		);
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Topic {
+	Expand,
+	Factorise,
+	Simplify,
+	CombineFractions,
+}
+
+impl fmt::Display for Topic {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Topic::Expand => write!(f, "Expand and simplify"),
+			Topic::Factorise => write!(f, "Factorise completely"),
+			Topic::Simplify => write!(f, "Simplify"),
+			Topic::CombineFractions => write!(f, "Express as a single fraction"),
+		}
+	}
+}
+
+// coefficients are drawn from the inclusive range
+// terms is the number of terms in each bracket, the number of like terms to collect,
+// or the number of fractions to combine
+// factors_nicely makes factorise questions quadratics with integer linear factors,
+// otherwise the question is a random polynomial factorised by taking out common factors
+#[derive(Debug, Clone)]
+pub struct Constraints {
+	pub coefficients: (i32, i32),
+	pub terms: usize,
+	pub factors_nicely: bool,
+	pub variable: String,
+}
+
+impl Default for Constraints {
+	fn default() -> Self {
+		Constraints {
+			coefficients: (-9, 9),
+			terms: 2,
+			factors_nicely: true,
+			variable: "x".to_string(),
+		}
+	}
+}
+
+#[derive(Debug, Clone)]
+pub struct Question {
+	pub topic: Topic,
+	pub question: Expression,
+	pub answer: Expression,
+}
+
+// returns None if the coefficient range has no nonzero values
+pub fn generate(topic: &Topic, constraints: &Constraints, rng: &mut Rng) -> Option<Question> {
+	let (question, answer) = match topic {
+		Topic::Expand => expand_question(constraints, rng),
+		Topic::Factorise => {
+			if constraints.factors_nicely {
+				factorise_question(constraints, rng)
+			} else {
+				common_factor_question(constraints, rng)
+			}
+		}
+		Topic::Simplify => simplify_question(constraints, rng),
+		Topic::CombineFractions => fraction_question(constraints, rng),
+	}?;
+	Some(Question {
+		topic: *topic,
+		question,
+		answer,
+	})
+}
+
+// count questions from a single seed
+pub fn generate_many(
+	topic: &Topic,
+	constraints: &Constraints,
+	seed: u64,
+	count: usize,
+) -> Option<Vec<Question>> {
+	let mut rng = Rng::new(seed);
+	(0..count)
+		.map(|_| generate(topic, constraints, &mut rng))
+		.collect()
+}
+
+// kx^n, left unsimplified
+fn monomial(k: i32, var: &str, n: usize) -> Expression {
+	let factors = match n {
+		0 => Vec::new(),
+		1 => vec![Box::new(var.into())],
+		_ => vec![Box::new(Expression::Exponent(Exponent {
+			base: Box::new(var.into()),
+			exponent: Box::new((n as i32).into()),
+		}))],
+	};
+	Expression::Product(Product {
+		coefficient: k.into(),
+		factors,
+	})
+}
+
+// a_{n-1}x^{n-1} + ... + a_0 with nonzero coefficients
+fn polynomial(constraints: &Constraints, rng: &mut Rng, terms: usize) -> Option<Expression> {
+	let (min, max) = constraints.coefficients;
+	let mut exp = Expression::Sum(Sum {
+		terms: (0..terms)
+			.rev()
+			.map(|n| {
+				Some(Box::new(monomial(
+					rng.nonzero(min, max)?,
+					&constraints.variable,
+					n,
+				)))
+			})
+			.collect::<Option<_>>()?,
+	});
+	exp.simplify();
+	Some(exp)
+}
+
+fn product(factors: Vec<Expression>) -> Expression {
+	Expression::Product(Product {
+		coefficient: 1.into(),
+		factors: factors.into_iter().map(Box::new).collect(),
+	})
+}
+
+const MAX_ATTEMPTS: usize = 1000;
+
+fn is_coprime(a: i32, b: i32) -> bool {
+	fraction_gcd(&a.into(), &b.into()).is_one()
+}
+
+// (ax + b)(cx + d)
+fn expand_question(constraints: &Constraints, rng: &mut Rng) -> Option<(Expression, Expression)> {
+	let terms = constraints.terms.max(2);
+	let question = product(vec![
+		polynomial(constraints, rng, terms)?,
+		polynomial(constraints, rng, terms)?,
+	]);
+	let mut answer = question.clone();
+	answer.expand_and_simplify();
+	Some((question, answer))
+}
+
+// (px + q)(rx + s) with p, r positive and each factor having no common factor
+// returns None if no such factor is found in the coefficient range
+fn factorise_question(
+	constraints: &Constraints,
+	rng: &mut Rng,
+) -> Option<(Expression, Expression)> {
+	let (min, max) = constraints.coefficients;
+	let factor = |rng: &mut Rng| {
+		for _ in 0..MAX_ATTEMPTS {
+			let p = rng.nonzero(min, max)?;
+			let q = rng.nonzero(min, max)?;
+			// p is positive unless the range has no positive values
+			if (p < 0 && max > 0) || !is_coprime(p, q) {
+				continue;
+			}
+			let mut exp = Expression::Sum(Sum {
+				terms: vec![
+					Box::new(monomial(p, &constraints.variable, 1)),
+					Box::new(q.into()),
+				],
+			});
+			exp.simplify();
+			return Some(exp);
+		}
+		None
+	};
+	let answer = product(vec![factor(rng)?, factor(rng)?]);
+	let mut question = answer.clone();
+	question.expand_and_simplify();
+	Some((question, answer))
+}
+
+// kx(a_{n-1}x^{n-1} + ... + a_0) expanded, with the answer from factorize
+fn common_factor_question(
+	constraints: &Constraints,
+	rng: &mut Rng,
+) -> Option<(Expression, Expression)> {
+	let (min, max) = constraints.coefficients;
+	let k = rng.nonzero(min, max)?;
+	let mut question = Expression::Product(Product {
+		coefficient: k.into(),
+		factors: vec![
+			Box::new(constraints.variable.as_str().into()),
+			Box::new(polynomial(constraints, rng, constraints.terms.max(2))?),
+		],
+	});
+	question.expand_and_simplify();
+	let mut answer = question.clone();
+	answer.factorize();
+	Some((question, answer))
+}
+
+// terms in x^2, x and constants to collect, shown unsimplified
+fn simplify_question(constraints: &Constraints, rng: &mut Rng) -> Option<(Expression, Expression)> {
+	let (min, max) = constraints.coefficients;
+	// at least one pair of like terms
+	let terms = constraints.terms.max(3);
+	let mut powers: Vec<usize> = Vec::new();
+	let question = Expression::Sum(Sum {
+		terms: (0..terms)
+			.map(|i| {
+				let mut n = rng.range(0, 2) as usize;
+				// repeat an earlier power if there is no pair yet
+				let mut distinct = powers.clone();
+				distinct.sort();
+				distinct.dedup();
+				if i == terms - 1 && distinct.len() == powers.len() {
+					n = *rng.choose(&powers)?;
+				}
+				powers.push(n);
+				Some(Box::new(monomial(
+					rng.nonzero(min, max)?,
+					&constraints.variable,
+					n,
+				)))
+			})
+			.collect::<Option<_>>()?,
+	});
+	let mut answer = question.clone();
+	answer.simplify();
+	Some((question, answer))
+}
+
+// \frac{ax + b}{d} + ... with numeral denominators
+fn fraction_question(constraints: &Constraints, rng: &mut Rng) -> Option<(Expression, Expression)> {
+	let (_, max) = constraints.coefficients;
+	let question = Expression::Sum(Sum {
+		terms: (0..constraints.terms.max(2))
+			.map(|_| {
+				Some(Box::new(Expression::Quotient(Quotient {
+					numerator: Box::new(polynomial(constraints, rng, 2)?),
+					denominator: Box::new(Fraction::from(rng.range(2, max.max(2))).into()),
+				})))
+			})
+			.collect::<Option<_>>()?,
+	});
+	let mut answer = question.clone();
+	answer.combine_fraction();
+	Some((question, answer))
+}
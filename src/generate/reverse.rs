@@ -0,0 +1,149 @@
+use crate::equation::Equation;
+use crate::expression::{Expression, Fraction, Product, Sum};
+use crate::generate::{Constraints, Question, Rng, Topic};
+
+#[cfg(test)]
+mod tests {
+	use crate::calculus::*;
+	use crate::equation::linear_coefficients;
+	use crate::expression::*;
+	use crate::generate::*;
+	use crate::*;
+
+	#[test]
+	fn from_factors() {
+		let q = factorise_from_factors(&[sum!(prod!(2, "x"), 3), sum!("x", -5)]);
+		assert_eq!(q.question.to_string(), "2x^2 - 7x - 15");
+		assert_eq!(
+			q.answer.to_string(),
+			"\\left( 2x + 3 \\right)\\left( x - 5 \\right)"
+		);
+		let q = factorise_from_factors(&[prod!(3, "x"), sum!("x", 1), sum!("x", -1)]);
+		assert_eq!(q.question.to_string(), "3x^3 - 3x");
+	}
+
+	#[test]
+	fn linear_equation() {
+		let mut rng = Rng::new(11);
+		let constraints = Constraints::default();
+		let solution = Fraction::new(3, 4);
+		for _ in 0..20 {
+			let eq = linear_equation_with_solution("x", &solution, &constraints, &mut rng).unwrap();
+			let (coefficients, constant) = eq.linear_coefficients().unwrap();
+			assert_eq!((constant / coefficients["x"]).negative(), solution);
+		}
+		let eq = linear_equation_with_solution("x", &solution, &constraints, &mut Rng::new(1)).unwrap();
+		assert_eq!(eq.to_string(), "- 5x + 5 = - x + 2");
+		// every coefficient stays in range
+		let constraints = Constraints {
+			coefficients: (-2, 2),
+			..Constraints::default()
+		};
+		let solution = Fraction::new(1, 2);
+		for _ in 0..20 {
+			let eq = linear_equation_with_solution("x", &solution, &constraints, &mut rng).unwrap();
+			let (coefficients, constant) = eq.linear_coefficients().unwrap();
+			assert_eq!((constant / coefficients["x"]).negative(), solution);
+			for side in [&eq.lhs, &eq.rhs] {
+				let (coefficients, constant) = linear_coefficients(side).unwrap();
+				assert!(constant.abs() <= 2.into());
+				assert!(coefficients.values().all(|c| c.abs() <= 2.into()));
+			}
+		}
+		// the solution cannot be reached with coefficients in range
+		let solution = Fraction::new(1, 9);
+		assert!(linear_equation_with_solution("x", &solution, &constraints, &mut rng).is_none());
+	}
+
+	#[test]
+	fn quadratic_equation() {
+		let eq = quadratic_with_roots("x", &[Fraction::new(3, 4), (-2).into()]);
+		assert_eq!(eq.to_string(), "4x^2 + 5x - 6 = 0");
+		let c = polynomial_coefficients(&eq.to_expression(), "x").unwrap();
+		let roots: Vec<String> = polynomial_roots(&c)
+			.unwrap()
+			.iter()
+			.map(|(r, _)| r.to_string())
+			.collect();
+		assert_eq!(roots, vec!["- 2", "\\frac{3}{4}"]);
+		let eq = quadratic_with_roots("x", &[Fraction::new(-1, 3), Fraction::new(-1, 3)]);
+		assert_eq!(eq.to_string(), "9x^2 + 6x + 1 = 0");
+	}
+}
+
+// expands the product of the factors into a factorise question
+pub fn factorise_from_factors(factors: &[Expression]) -> Question {
+	let answer = Expression::Product(Product {
+		coefficient: 1.into(),
+		factors: factors.iter().map(|f| Box::new(f.clone())).collect(),
+	});
+	let mut question = answer.clone();
+	question.expand_and_simplify();
+	Question {
+		topic: Topic::Factorise,
+		question,
+		answer,
+	}
+}
+
+// ax + b
+fn linear(a: i32, var: &str, b: i32) -> Expression {
+	let mut exp = Expression::Sum(Sum {
+		terms: vec![
+			Box::new(Expression::Product(Product {
+				coefficient: a.into(),
+				factors: vec![Box::new(var.into())],
+			})),
+			Box::new(b.into()),
+		],
+	});
+	exp.simplify();
+	exp
+}
+
+// ax + b = cx + d with integer coefficients and the given solution
+// a - c is a multiple of the denominator of the solution so that d is an integer
+// a, b, c and d are all drawn from the coefficient range
+// returns None if no such equation is found
+pub fn linear_equation_with_solution(
+	var: &str,
+	solution: &Fraction,
+	constraints: &Constraints,
+	rng: &mut Rng,
+) -> Option<Equation> {
+	let (min, max) = constraints.coefficients;
+	let in_range = |n: i64| (min as i64..=max as i64).contains(&n);
+	for _ in 0..MAX_ATTEMPTS {
+		let k = solution.denominator as i64 * rng.nonzero(-3, 3)? as i64;
+		let c = rng.range(min, max);
+		let b = rng.range(min, max);
+		let a = c as i64 + k;
+		// (a - c) x = d - b
+		let d = b as i64 + k / solution.denominator as i64 * solution.numerator as i64;
+		if a != 0 && in_range(a) && in_range(d) {
+			return Some(Equation {
+				lhs: Box::new(linear(a as i32, var, b)),
+				rhs: Box::new(linear(c, var, d as i32)),
+			});
+		}
+	}
+	None
+}
+
+const MAX_ATTEMPTS: usize = 1000;
+
+// ax^2 + bx + c = 0 from (q_1 x - p_1)(q_2 x - p_2) for roots \frac{p_1}{q_1} and \frac{p_2}{q_2}
+pub fn quadratic_with_roots(var: &str, roots: &[Fraction; 2]) -> Equation {
+	let mut lhs = Expression::Product(Product {
+		coefficient: 1.into(),
+		factors: roots
+			.iter()
+			.map(|r| Box::new(linear(r.denominator as i32, var, -r.numerator)))
+			.collect(),
+	});
+	lhs.expand_and_simplify();
+	Equation {
+		lhs: Box::new(lhs),
+		rhs: Box::new(0.into()),
+	}
+}
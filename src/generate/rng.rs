@@ -0,0 +1,78 @@
+#[cfg(test)]
+mod tests {
+	use crate::generate::Rng;
+
+	#[test]
+	fn reproducible() {
+		let mut a = Rng::new(42);
+		let mut b = Rng::new(42);
+		let x: Vec<u64> = (0..5).map(|_| a.next_u64()).collect();
+		let y: Vec<u64> = (0..5).map(|_| b.next_u64()).collect();
+		assert_eq!(x, y);
+		let mut c = Rng::new(43);
+		assert_ne!(x[0], c.next_u64());
+	}
+
+	#[test]
+	fn ranges() {
+		let mut rng = Rng::new(7);
+		for _ in 0..200 {
+			let n = rng.range(-3, 5);
+			assert!((-3..=5).contains(&n));
+			assert_ne!(rng.nonzero(-2, 2), Some(0));
+		}
+		assert_eq!(rng.range(4, 4), 4);
+		assert_eq!(rng.nonzero(0, 0), None);
+		assert!(["a", "b"].contains(rng.choose(&["a", "b"]).unwrap()));
+		assert!(rng.choose::<i32>(&[]).is_none());
+	}
+}
+
+// a small seeded generator (SplitMix64) so that questions can be reproduced from a seed
+#[derive(Debug, Clone)]
+pub struct Rng {
+	state: u64,
+}
+
+impl Rng {
+	pub fn new(seed: u64) -> Rng {
+		Rng { state: seed }
+	}
+
+	pub fn next_u64(&mut self) -> u64 {
+		self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+		let mut z = self.state;
+		z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+		z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+		z ^ (z >> 31)
+	}
+
+	// uniform in min..=max
+	pub fn range(&mut self, min: i32, max: i32) -> i32 {
+		let (min, max) = (min.min(max), min.max(max));
+		let size = (max as i64 - min as i64 + 1) as u64;
+		(min as i64 + (self.next_u64() % size) as i64) as i32
+	}
+
+	// uniform in min..=max without 0
+	// returns None if 0 is the only choice
+	pub fn nonzero(&mut self, min: i32, max: i32) -> Option<i32> {
+		if min == 0 && max == 0 {
+			return None;
+		}
+		loop {
+			let n = self.range(min, max);
+			if n != 0 {
+				return Some(n);
+			}
+		}
+	}
+
+	// returns None if there are no items
+	pub fn choose<'a, T>(&mut self, items: &'a [T]) -> Option<&'a T> {
+		if items.is_empty() {
+			return None;
+		}
+		Some(&items[self.range(0, items.len() as i32 - 1) as usize])
+	}
+}
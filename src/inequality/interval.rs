@@ -0,0 +1,194 @@
+use crate::expression::Fraction;
+use std::fmt;
+
+#[cfg(test)]
+mod tests {
+	use crate::expression::*;
+	use crate::inequality::*;
+
+	#[test]
+	fn display() {
+		let interval = Interval {
+			lower: Some((Fraction::new(-1, 2), true)),
+			upper: Some((3.into(), false)),
+		};
+		assert_eq!(interval.to_string(), "\\left[ - \\frac{1}{2}, 3 \\right)");
+		assert_eq!(interval.to_inequality("x"), "- \\frac{1}{2} \\leq x < 3");
+		let interval = Interval {
+			lower: Some((2.into(), false)),
+			upper: None,
+		};
+		assert_eq!(interval.to_string(), "\\left( 2, \\infty \\right)");
+		assert_eq!(interval.to_inequality("x"), "x > 2");
+		let interval = Interval {
+			lower: None,
+			upper: None,
+		};
+		assert_eq!(interval.to_string(), "\\left( - \\infty, \\infty \\right)");
+		assert_eq!(interval.to_inequality("x"), "x \\in \\mathbb{R}");
+	}
+
+	#[test]
+	fn number_line() {
+		let interval = Interval {
+			lower: Some(((-2).into(), false)),
+			upper: Some((3.into(), true)),
+		};
+		assert_eq!(
+			interval.to_number_line(),
+			"\\begin{tikzpicture}\n\
+			\\draw[<->] (-4,0) -- (5,0);\n\
+			\\foreach \\x in {-3,...,4} \\draw (\\x,0.1) -- (\\x,-0.1) node[below] {$\\x$};\n\
+			\\draw[very thick] (-2,0.3) -- (3,0.3);\n\
+			\\draw (-2,0) -- (-2,0.3);\n\
+			\\draw[fill=white] (-2,0.3) circle (0.1);\n\
+			\\draw (3,0) -- (3,0.3);\n\
+			\\draw[fill=black] (3,0.3) circle (0.1);\n\
+			\\end{tikzpicture}"
+		);
+		let interval = Interval {
+			lower: None,
+			upper: Some((Fraction::new(1, 2), false)),
+		};
+		assert_eq!(
+			interval.to_number_line(),
+			"\\begin{tikzpicture}\n\
+			\\draw[<->] (-2,0) -- (3,0);\n\
+			\\foreach \\x in {-1,...,2} \\draw (\\x,0.1) -- (\\x,-0.1) node[below] {$\\x$};\n\
+			\\draw[very thick,->] (0.5,0.3) -- (-2,0.3);\n\
+			\\draw (0.5,0) -- (0.5,0.3);\n\
+			\\draw[fill=white] (0.5,0.3) circle (0.1);\n\
+			\\node[above] at (0.5,0.4) {$\\frac{1}{2}$};\n\
+			\\end{tikzpicture}"
+		);
+	}
+}
+
+// a set of real numbers between two endpoints
+// each endpoint is (value, included), with None representing infinity
+#[derive(Debug, Clone, PartialEq)]
+pub struct Interval {
+	pub lower: Option<(Fraction, bool)>,
+	pub upper: Option<(Fraction, bool)>,
+}
+
+// interval notation
+impl fmt::Display for Interval {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let (left, lower) = match self.lower {
+			Some((n, true)) => ("[", n.to_string()),
+			Some((n, false)) => ("(", n.to_string()),
+			None => ("(", "- \\infty".to_string()),
+		};
+		let (right, upper) = match self.upper {
+			Some((n, true)) => ("]", n.to_string()),
+			Some((n, false)) => (")", n.to_string()),
+			None => (")", "\\infty".to_string()),
+		};
+		write!(f, "\\left{} {}, {} \\right{}", left, lower, upper, right)
+	}
+}
+
+impl Interval {
+	pub fn is_empty(&self) -> bool {
+		if let (Some((a, a_closed)), Some((b, b_closed))) = (self.lower, self.upper) {
+			return a > b || (a == b && !(a_closed && b_closed));
+		}
+		false
+	}
+
+	pub fn contains(&self, x: &Fraction) -> bool {
+		let above_lower = match self.lower {
+			Some((a, true)) => x >= &a,
+			Some((a, false)) => x > &a,
+			None => true,
+		};
+		let below_upper = match self.upper {
+			Some((b, true)) => x <= &b,
+			Some((b, false)) => x < &b,
+			None => true,
+		};
+		above_lower && below_upper
+	}
+
+	// x < 3, -2 < x <= 3, etc
+	pub fn to_inequality(&self, var: &str) -> String {
+		let lower_sign = |closed: bool| if closed { "\\leq" } else { "<" };
+		match (self.lower, self.upper) {
+			(Some((a, a_closed)), Some((b, b_closed))) => {
+				format!(
+					"{} {} {} {} {}",
+					a,
+					lower_sign(a_closed),
+					var,
+					lower_sign(b_closed),
+					b
+				)
+			}
+			(Some((a, closed)), None) => {
+				format!("{} {} {}", var, if closed { "\\geq" } else { ">" }, a)
+			}
+			(None, Some((b, closed))) => format!("{} {} {}", var, lower_sign(closed), b),
+			(None, None) => format!("{} \\in \\mathbb{{R}}", var),
+		}
+	}
+
+	// tikz number line with integer ticks, shading the interval above the line
+	// filled circles for included endpoints and hollow circles otherwise
+	pub fn to_number_line(&self) -> String {
+		let endpoints: Vec<Fraction> = [self.lower, self.upper]
+			.iter()
+			.flatten()
+			.map(|(n, _)| *n)
+			.collect();
+		let min = endpoints.iter().map(floor).min().unwrap_or(0) - 2;
+		let max = endpoints.iter().map(ceil).max().unwrap_or(0) + 2;
+		let mut lines: Vec<String> = vec![
+			"\\begin{tikzpicture}".to_string(),
+			format!("\\draw[<->] ({},0) -- ({},0);", min, max),
+			format!(
+				"\\foreach \\x in {{{},...,{}}} \\draw (\\x,0.1) -- (\\x,-0.1) node[below] {{$\\x$}};",
+				min + 1,
+				max - 1
+			),
+		];
+		let start = self.lower.map(|(n, _)| coordinate(&n));
+		let end = self.upper.map(|(n, _)| coordinate(&n));
+		let line = match (start, end) {
+			(Some(a), Some(b)) => format!("\\draw[very thick] ({},0.3) -- ({},0.3);", a, b),
+			(Some(a), None) => format!("\\draw[very thick,->] ({},0.3) -- ({},0.3);", a, max),
+			(None, Some(b)) => format!("\\draw[very thick,->] ({},0.3) -- ({},0.3);", b, min),
+			(None, None) => format!("\\draw[very thick,<->] ({},0.3) -- ({},0.3);", min, max),
+		};
+		lines.push(line);
+		for (n, closed) in [self.lower, self.upper].iter().flatten() {
+			let x = coordinate(n);
+			let fill = if *closed { "black" } else { "white" };
+			lines.push(format!("\\draw ({},0) -- ({},0.3);", x, x));
+			lines.push(format!("\\draw[fill={}] ({},0.3) circle (0.1);", fill, x));
+			// non-integer endpoints do not have a tick label
+			if !n.is_integer() {
+				lines.push(format!("\\node[above] at ({},0.4) {{${}$}};", x, n));
+			}
+		}
+		lines.push("\\end{tikzpicture}".to_string());
+		lines.join("\n")
+	}
+}
+
+fn floor(n: &Fraction) -> i32 {
+	n.numerator.div_euclid(n.denominator as i32)
+}
+
+fn ceil(n: &Fraction) -> i32 {
+	-(n.negative().numerator.div_euclid(n.denominator as i32))
+}
+
+fn coordinate(n: &Fraction) -> String {
+	if n.is_integer() {
+		n.numerator.to_string()
+	} else {
+		let x = n.numerator as f64 / n.denominator as f64;
+		format!("{:.3}", x).trim_end_matches('0').to_string()
+	}
+}
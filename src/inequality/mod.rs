@@ -0,0 +1,279 @@
+use crate::equation::{linear_coefficients, Equation};
+use crate::expression::{Expression, Fraction};
+use std::fmt;
+pub mod interval;
+pub mod modulus;
+pub use interval::Interval;
+
+#[cfg(test)]
+mod tests {
+	use crate::expression::*;
+	use crate::inequality::*;
+	use crate::*;
+
+	#[test]
+	fn display() {
+		let ineq = Inequality {
+			lhs: Box::new(sum!(prod!(2, "x"), 1)),
+			sign: InequalitySign::LessThanOrEqual,
+			rhs: Box::new(7.into()),
+		};
+		assert_eq!(ineq.to_string(), "2x + 1 \\leq 7");
+	}
+
+	#[test]
+	fn solve() {
+		// 2x + 1 <= 7
+		let ineq = Inequality {
+			lhs: Box::new(sum!(prod!(2, "x"), 1)),
+			sign: InequalitySign::LessThanOrEqual,
+			rhs: Box::new(7.into()),
+		};
+		assert_eq!(ineq.solve("x").unwrap().to_string(), "x \\leq 3");
+		// 5 - 3x > x + 1
+		let ineq = Inequality {
+			lhs: Box::new(sum!(5, prod!(-3, "x"))),
+			sign: InequalitySign::GreaterThan,
+			rhs: Box::new(sum!("x", 1)),
+		};
+		assert_eq!(ineq.solve("x").unwrap().to_string(), "x < 1");
+		let interval = ineq.solution_interval("x").unwrap();
+		assert_eq!(interval.to_string(), "\\left( - \\infty, 1 \\right)");
+		// (x - 1)/2 >= x/3
+		let ineq = Inequality {
+			lhs: Box::new(quotient!(sum!("x", -1), 2)),
+			sign: InequalitySign::GreaterThanOrEqual,
+			rhs: Box::new(quotient!("x", 3)),
+		};
+		assert_eq!(ineq.solve("x").unwrap().to_string(), "x \\geq 3");
+		let ineq = Inequality {
+			lhs: Box::new(prod!("x", "x")),
+			sign: InequalitySign::GreaterThan,
+			rhs: Box::new(0.into()),
+		};
+		assert!(ineq.solve("x").is_none());
+	}
+
+	#[test]
+	fn compound() {
+		// -3 < 2x + 1 <= 7
+		let ineq = CompoundInequality {
+			lower: Box::new((-3).into()),
+			lower_sign: InequalitySign::LessThan,
+			middle: Box::new(sum!(prod!(2, "x"), 1)),
+			upper_sign: InequalitySign::LessThanOrEqual,
+			upper: Box::new(7.into()),
+		};
+		assert_eq!(ineq.to_string(), "- 3 < 2x + 1 \\leq 7");
+		assert_eq!(ineq.solve("x").unwrap().to_string(), "- 2 < x \\leq 3");
+		let interval = ineq.solution_interval("x").unwrap();
+		assert_eq!(interval.to_string(), "\\left( - 2, 3 \\right]");
+		// 1 <= 4 - 3x < 10
+		let ineq = CompoundInequality {
+			lower: Box::new(1.into()),
+			lower_sign: InequalitySign::LessThanOrEqual,
+			middle: Box::new(sum!(4, prod!(-3, "x"))),
+			upper_sign: InequalitySign::LessThan,
+			upper: Box::new(10.into()),
+		};
+		assert_eq!(ineq.solve("x").unwrap().to_string(), "- 2 < x \\leq 1");
+		let ineq = CompoundInequality {
+			lower: Box::new(1.into()),
+			lower_sign: InequalitySign::LessThan,
+			middle: Box::new("x".into()),
+			upper_sign: InequalitySign::LessThan,
+			upper: Box::new(0.into()),
+		};
+		assert!(ineq.solution_interval("x").unwrap().is_empty());
+		// 1 < x > 3
+		let ineq = CompoundInequality {
+			lower: Box::new(1.into()),
+			lower_sign: InequalitySign::LessThan,
+			middle: Box::new("x".into()),
+			upper_sign: InequalitySign::GreaterThan,
+			upper: Box::new(3.into()),
+		};
+		assert!(ineq.solution_interval("x").is_none());
+		assert!(ineq.solve("x").is_none());
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InequalitySign {
+	LessThan,
+	LessThanOrEqual,
+	GreaterThan,
+	GreaterThanOrEqual,
+}
+
+impl fmt::Display for InequalitySign {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			InequalitySign::LessThan => write!(f, "<"),
+			InequalitySign::LessThanOrEqual => write!(f, "\\leq"),
+			InequalitySign::GreaterThan => write!(f, ">"),
+			InequalitySign::GreaterThanOrEqual => write!(f, "\\geq"),
+		}
+	}
+}
+
+impl InequalitySign {
+	// sign after multiplying or dividing both sides by a negative number
+	pub fn flip(&self) -> InequalitySign {
+		match self {
+			InequalitySign::LessThan => InequalitySign::GreaterThan,
+			InequalitySign::LessThanOrEqual => InequalitySign::GreaterThanOrEqual,
+			InequalitySign::GreaterThan => InequalitySign::LessThan,
+			InequalitySign::GreaterThanOrEqual => InequalitySign::LessThanOrEqual,
+		}
+	}
+
+	pub fn is_strict(&self) -> bool {
+		matches!(self, InequalitySign::LessThan | InequalitySign::GreaterThan)
+	}
+
+	pub fn is_less_than(&self) -> bool {
+		matches!(
+			self,
+			InequalitySign::LessThan | InequalitySign::LessThanOrEqual
+		)
+	}
+}
+
+#[derive(Debug, Clone)]
+pub struct Inequality {
+	pub lhs: Box<Expression>,
+	pub sign: InequalitySign,
+	pub rhs: Box<Expression>,
+}
+
+impl fmt::Display for Inequality {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{} {} {}", self.lhs, self.sign, self.rhs)
+	}
+}
+
+impl Inequality {
+	// solves a linear inequality in var
+	// returns x < k, x <= k, x > k or x >= k
+	pub fn solve(&self, var: &str) -> Option<Inequality> {
+		let (value, sign) = self.solve_for_value(var)?;
+		Some(Inequality {
+			lhs: Box::new(Expression::Variable(var.to_string())),
+			sign,
+			rhs: Box::new(Expression::Numeral(value)),
+		})
+	}
+
+	pub fn solution_interval(&self, var: &str) -> Option<Interval> {
+		let (value, sign) = self.solve_for_value(var)?;
+		let interval = match sign {
+			InequalitySign::LessThan | InequalitySign::LessThanOrEqual => Interval {
+				lower: None,
+				upper: Some((value, !sign.is_strict())),
+			},
+			_ => Interval {
+				lower: Some((value, !sign.is_strict())),
+				upper: None,
+			},
+		};
+		Some(interval)
+	}
+
+	// ax + c (sign) 0 => x (sign) -c/a, flipping the sign if a < 0
+	fn solve_for_value(&self, var: &str) -> Option<(Fraction, InequalitySign)> {
+		let (a, c) = linear_in(&self.lhs, &self.rhs, var)?;
+		let value = c.negative() / a;
+		let sign = if a.is_negative() {
+			self.sign.flip()
+		} else {
+			self.sign
+		};
+		Some((value, sign))
+	}
+}
+
+// a < f(x) <= b, where the signs are either both < / <= or both > / >=
+#[derive(Debug, Clone)]
+pub struct CompoundInequality {
+	pub lower: Box<Expression>,
+	pub lower_sign: InequalitySign,
+	pub middle: Box<Expression>,
+	pub upper_sign: InequalitySign,
+	pub upper: Box<Expression>,
+}
+
+impl fmt::Display for CompoundInequality {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(
+			f,
+			"{} {} {} {} {}",
+			self.lower, self.lower_sign, self.middle, self.upper_sign, self.upper
+		)
+	}
+}
+
+impl CompoundInequality {
+	// solves a < f(x) < b for linear f, returning k_1 < x < k_2
+	pub fn solve(&self, var: &str) -> Option<CompoundInequality> {
+		let interval = self.solution_interval(var)?;
+		let (lower, lower_closed) = interval.lower?;
+		let (upper, upper_closed) = interval.upper?;
+		Some(CompoundInequality {
+			lower: Box::new(Expression::Numeral(lower)),
+			lower_sign: less_than(lower_closed),
+			middle: Box::new(Expression::Variable(var.to_string())),
+			upper_sign: less_than(upper_closed),
+			upper: Box::new(Expression::Numeral(upper)),
+		})
+	}
+
+	// returns None if the two signs point in different directions, e.g. a < x > b
+	pub fn solution_interval(&self, var: &str) -> Option<Interval> {
+		if self.lower_sign.is_less_than() != self.upper_sign.is_less_than() {
+			return None;
+		}
+		let (a, c) = linear_in(&self.middle, &Expression::Numeral(0.into()), var)?;
+		let lower: Fraction = self.lower.as_ref().clone().try_into().ok()?;
+		let upper: Fraction = self.upper.as_ref().clone().try_into().ok()?;
+		// write as lower < f(x) < upper
+		let (lower, lower_sign, upper, upper_sign) = if self.lower_sign.is_less_than() {
+			(lower, self.lower_sign, upper, self.upper_sign)
+		} else {
+			(upper, self.upper_sign.flip(), lower, self.lower_sign.flip())
+		};
+		let lower = (lower - c) / a;
+		let upper = (upper - c) / a;
+		let (lower, lower_sign, upper, upper_sign) = if a.is_negative() {
+			(upper, upper_sign, lower, lower_sign)
+		} else {
+			(lower, lower_sign, upper, upper_sign)
+		};
+		Some(Interval {
+			lower: Some((lower, !lower_sign.is_strict())),
+			upper: Some((upper, !upper_sign.is_strict())),
+		})
+	}
+}
+
+fn less_than(closed: bool) -> InequalitySign {
+	if closed {
+		InequalitySign::LessThanOrEqual
+	} else {
+		InequalitySign::LessThan
+	}
+}
+
+// lhs - rhs = ax + c, where a is nonzero and no other variables appear
+fn linear_in(lhs: &Expression, rhs: &Expression, var: &str) -> Option<(Fraction, Fraction)> {
+	let eq = Equation {
+		lhs: Box::new(lhs.clone()),
+		rhs: Box::new(rhs.clone()),
+	};
+	let (coefficients, constant) = linear_coefficients(&eq.to_expression())?;
+	if coefficients.keys().any(|v| v != var) {
+		return None;
+	}
+	let a = coefficients.get(var)?;
+	Some((*a, constant))
+}
@@ -0,0 +1,139 @@
+use crate::equation::modulus_argument;
+use crate::expression::{Expression, Fraction};
+use crate::inequality::{CompoundInequality, Inequality, InequalitySign, Interval};
+
+#[cfg(test)]
+mod tests {
+	use crate::expression::*;
+	use crate::inequality::*;
+	use crate::*;
+
+	#[test]
+	fn modulus_solution() {
+		// |2x - 1| < 5
+		let ineq = Inequality {
+			lhs: Box::new(abs!(sum!(prod!(2, "x"), -1))),
+			sign: InequalitySign::LessThan,
+			rhs: Box::new(5.into()),
+		};
+		let intervals = ineq.modulus_solution("x").unwrap();
+		assert_eq!(intervals.len(), 1);
+		assert_eq!(intervals[0].to_inequality("x"), "- 2 < x < 3");
+		// |3 - x| >= 2
+		let ineq = Inequality {
+			lhs: Box::new(abs!(sum!(3, prod!(-1, "x")))),
+			sign: InequalitySign::GreaterThanOrEqual,
+			rhs: Box::new(2.into()),
+		};
+		let intervals = ineq.modulus_solution("x").unwrap();
+		assert_eq!(intervals[0].to_inequality("x"), "x \\leq 1");
+		assert_eq!(intervals[1].to_inequality("x"), "x \\geq 5");
+		// 4 > |x|
+		let ineq = Inequality {
+			lhs: Box::new(4.into()),
+			sign: InequalitySign::GreaterThan,
+			rhs: Box::new(abs!("x")),
+		};
+		let intervals = ineq.modulus_solution("x").unwrap();
+		assert_eq!(intervals[0].to_string(), "\\left( - 4, 4 \\right)");
+		// |x| < -1 has no solutions, |x| > -1 holds for all x
+		let ineq = Inequality {
+			lhs: Box::new(abs!("x")),
+			sign: InequalitySign::LessThan,
+			rhs: Box::new((-1).into()),
+		};
+		assert!(ineq.modulus_solution("x").unwrap().is_empty());
+		let ineq = Inequality {
+			lhs: Box::new(abs!("x")),
+			sign: InequalitySign::GreaterThan,
+			rhs: Box::new((-1).into()),
+		};
+		assert_eq!(
+			ineq.modulus_solution("x").unwrap()[0].to_inequality("x"),
+			"x \\in \\mathbb{R}"
+		);
+		// |x| >= 0 holds for all x, |x| > 0 leaves out 0
+		let ineq = Inequality {
+			lhs: Box::new(abs!("x")),
+			sign: InequalitySign::GreaterThanOrEqual,
+			rhs: Box::new(0.into()),
+		};
+		let intervals = ineq.modulus_solution("x").unwrap();
+		assert_eq!(intervals.len(), 1);
+		assert_eq!(intervals[0].to_inequality("x"), "x \\in \\mathbb{R}");
+		let ineq = Inequality {
+			lhs: Box::new(abs!("x")),
+			sign: InequalitySign::GreaterThan,
+			rhs: Box::new(0.into()),
+		};
+		assert_eq!(ineq.modulus_solution("x").unwrap().len(), 2);
+	}
+}
+
+impl Inequality {
+	// |f| < a becomes -a < f < a, and |f| > a becomes f < -a or f > a
+	// a must be a numeral and f must be linear
+	// returns the solution as a list of disjoint intervals in increasing order
+	pub fn modulus_solution(&self, var: &str) -> Option<Vec<Interval>> {
+		// write as |f| (sign) a
+		let (f, sign, a) = match modulus_argument(&self.lhs) {
+			Some(f) => (f, self.sign, self.rhs.as_ref()),
+			None => (
+				modulus_argument(&self.rhs)?,
+				self.sign.flip(),
+				self.lhs.as_ref(),
+			),
+		};
+		let a: Fraction = a.clone().try_into().ok()?;
+		let intervals = match sign {
+			InequalitySign::LessThan | InequalitySign::LessThanOrEqual => {
+				let interval = CompoundInequality {
+					lower: Box::new(Expression::Numeral(a.negative())),
+					lower_sign: sign,
+					middle: Box::new(f.clone()),
+					upper_sign: sign,
+					upper: Box::new(Expression::Numeral(a)),
+				}
+				.solution_interval(var)?;
+				if interval.is_empty() {
+					Vec::new()
+				} else {
+					vec![interval]
+				}
+			}
+			_ => {
+				if a.is_negative() {
+					return Some(vec![Interval {
+						lower: None,
+						upper: None,
+					}]);
+				}
+				let below = Inequality {
+					lhs: Box::new(f.clone()),
+					sign: sign.flip(),
+					rhs: Box::new(Expression::Numeral(a.negative())),
+				};
+				let above = Inequality {
+					lhs: Box::new(f.clone()),
+					sign,
+					rhs: Box::new(Expression::Numeral(a)),
+				};
+				let mut intervals = vec![below.solution_interval(var)?, above.solution_interval(var)?];
+				// the lower piece is the one unbounded below
+				intervals.sort_by_key(|i| i.lower.is_some());
+				// pieces that touch or overlap, as in |x| >= 0, cover every x
+				if let (Some((p, p_closed)), Some((q, q_closed))) = (intervals[0].upper, intervals[1].lower)
+				{
+					if p > q || (p == q && (p_closed || q_closed)) {
+						return Some(vec![Interval {
+							lower: None,
+							upper: None,
+						}]);
+					}
+				}
+				intervals
+			}
+		};
+		Some(intervals)
+	}
+}
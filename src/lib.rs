@@ -1 +1,7 @@
+pub mod calculus;
+pub mod equation;
 pub mod expression;
+pub mod generate;
+pub mod inequality;
+pub mod marking;
+pub mod worksheet;
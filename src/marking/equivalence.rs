@@ -0,0 +1,387 @@
+use crate::calculus::approximate;
+use crate::expression::{Expression, Fn, Fraction, SubIn, E};
+use crate::generate::Rng;
+
+#[cfg(test)]
+mod tests {
+	use crate::expression::*;
+	use crate::marking::*;
+	use crate::*;
+
+	#[test]
+	fn variables() {
+		let exp = sum!(prod!("y", "x"), ln!("x"), exp_e!("z"), sin!("\\pi"));
+		assert_eq!(exp.variables(), vec!["x", "y", "z"]);
+	}
+
+	#[test]
+	fn equivalent() {
+		let expected = exp!(sum!("x", 1), 2);
+		let student = sum!(exp!("x", 2), prod!(2, "x"), 1);
+		assert_eq!(
+			check_equivalence(&expected, &student),
+			Equivalence::Equivalent
+		);
+		// only equal away from x = 1 and x = -1
+		let expected = quotient!(2, sum!(exp!("x", 2), -1));
+		let student = Expression::Sum(Sum {
+			terms: vec![
+				Box::new(quotient!(1, sum!("x", -1))),
+				Box::new(prod!(-1, quotient!(1, sum!("x", 1)))),
+			],
+		});
+		assert_eq!(
+			check_equivalence(&expected, &student),
+			Equivalence::Equivalent
+		);
+		let expected = quotient!(sum!(exp!("x", 2), prod!(-1, exp!("y", 2))), sum!("x", "y"));
+		assert_eq!(
+			check_equivalence(&expected, &sum!("x", prod!(-1, "y"))),
+			Equivalence::Equivalent
+		);
+		assert_eq!(
+			check_equivalence(&sqrt!(8), &prod!(2, sqrt!(2))),
+			Equivalence::Equivalent
+		);
+	}
+
+	#[test]
+	fn not_equivalent() {
+		let expected = exp!("x", 2);
+		assert_eq!(
+			check_equivalence(&expected, &prod!(2, "x")),
+			Equivalence::NotEquivalent
+		);
+		let expected = exp!(sum!("x", 3), 2);
+		let student = sum!(exp!("x", 2), 9);
+		assert_eq!(
+			check_equivalence(&expected, &student),
+			Equivalence::NotEquivalent
+		);
+		assert_eq!(
+			check_equivalence(&Fraction::new(1, 2).into(), &Fraction::new(2, 3).into()),
+			Equivalence::NotEquivalent
+		);
+		assert_eq!(
+			check_equivalence(&sqrt!("x"), &quotient!("x", 2)),
+			Equivalence::NotEquivalent
+		);
+	}
+
+	#[test]
+	fn large_powers() {
+		let exp = exp!(sum!("x", 1), 20);
+		assert_eq!(check_equivalence(&exp, &exp), Equivalence::Equivalent);
+		assert_eq!(
+			check_equivalence(&exp, &exp!(sum!(1, "x"), 20)),
+			Equivalence::Equivalent
+		);
+		let expected = exp!(sum!(prod!(5, "x"), 3), 12);
+		let student = exp!(sum!("x", 2), 12);
+		assert_eq!(
+			check_equivalence(&expected, &student),
+			Equivalence::NotEquivalent
+		);
+	}
+
+	#[test]
+	fn domains() {
+		let expected = sqrt!(exp!("x", 2));
+		assert_eq!(
+			check_equivalence(&expected, &abs!("x")),
+			Equivalence::Equivalent
+		);
+		assert_eq!(
+			check_equivalence(&expected, &"x".into()),
+			Equivalence::NotEquivalent
+		);
+		assert_eq!(
+			check_equivalence(&prod!(2, ln!("x")), &ln!(exp!("x", 2))),
+			Equivalence::Equivalent
+		);
+		// defined only for x > 10
+		let shifted = sum!("x", -10);
+		assert_eq!(
+			check_equivalence(&ln!(shifted.clone()), &prod!(2, ln!(shifted.clone()))),
+			Equivalence::NotEquivalent
+		);
+		assert_eq!(
+			check_equivalence(&sqrt!(shifted.clone()), &prod!(2, sqrt!(shifted.clone()))),
+			Equivalence::NotEquivalent
+		);
+		assert_eq!(
+			check_equivalence(&prod!(2, ln!(shifted.clone())), &ln!(exp!(shifted, 2))),
+			Equivalence::Equivalent
+		);
+	}
+
+	#[test]
+	fn undetermined() {
+		// trig values at most rational angles are not exact
+		let student = cos!(sum!(90, prod!(-1, "x")));
+		assert_eq!(
+			check_equivalence(&sin!("x"), &student),
+			Equivalence::Undetermined
+		);
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Equivalence {
+	Equivalent,
+	NotEquivalent,
+	Undetermined,
+}
+
+// points that must agree before two expressions are taken to be equivalent
+const SAMPLES: usize = 6;
+const ATTEMPTS: usize = 40;
+// points in a row that cannot be compared before the sampling range is widened
+const MAX_MISSES: usize = 5;
+// bound on the size of the integers in exact working, leaving room below i32::MAX
+const MAX_EXACT: f64 = 1e8;
+// expanding takes time exponential in the power
+const MAX_EXPANDED_POWER: u32 = 8;
+
+// compares the expanded and simplified forms first,
+// then evaluates both at rational points where both are defined,
+// widening the range of points when too few of them are
+// expressions whose exact values could overflow, such as large powers, are compared in floating point
+// the points come from a fixed seed so that marking is repeatable
+pub fn check_equivalence(expected: &Expression, student: &Expression) -> Equivalence {
+	if expected.to_string() == student.to_string() {
+		return Equivalence::Equivalent;
+	}
+	if is_expandable(expected) && is_expandable(student) {
+		let mut a = expected.clone();
+		let mut b = student.clone();
+		a.expand_and_simplify();
+		b.expand_and_simplify();
+		if a.to_string() == b.to_string() {
+			return Equivalence::Equivalent;
+		}
+	}
+	let mut variables = expected.variables();
+	variables.extend(student.variables());
+	variables.sort();
+	variables.dedup();
+	let mut rng = Rng::new(0);
+	let mut agreed = 0;
+	// the range is doubled after repeated misses, in case the domain is far from 0
+	let mut range = 6;
+	let mut misses = 0;
+	for _ in 0..ATTEMPTS {
+		if misses == MAX_MISSES {
+			range *= 2;
+			misses = 0;
+		}
+		let point: Vec<(String, Expression)> = variables
+			.iter()
+			.map(|v| {
+				let value = Fraction::new(rng.range(-range, range), rng.range(1, 3));
+				(v.clone(), Expression::Numeral(value))
+			})
+			.collect();
+		let agrees = if is_exact(expected, &point) && is_exact(student, &point) {
+			if !is_defined(expected, &point) || !is_defined(student, &point) {
+				misses += 1;
+				continue;
+			}
+			compare(&evaluate(expected, &point), &evaluate(student, &point))
+				.or_else(|| compare_approximations(expected, student, &point))
+		} else {
+			compare_approximations(expected, student, &point)
+		};
+		match agrees {
+			Some(true) => agreed += 1,
+			Some(false) => return Equivalence::NotEquivalent,
+			None => misses += 1,
+		}
+		if agreed == SAMPLES || (variables.is_empty() && agreed > 0) {
+			return Equivalence::Equivalent;
+		}
+	}
+	Equivalence::Undetermined
+}
+
+fn evaluate(exp: &Expression, point: &[(String, Expression)]) -> Expression {
+	let mut exp = point
+		.iter()
+		.fold(exp.clone(), |exp, (var, value)| exp.sub_in(var, value));
+	exp.expand_and_simplify();
+	exp
+}
+
+// Some(true) if the values agree, Some(false) if they differ and None if they cannot be compared
+fn compare(a: &Expression, b: &Expression) -> Option<bool> {
+	if let (Expression::Numeral(x), Expression::Numeral(y)) = (a, b) {
+		return Some(x == y);
+	}
+	if a.to_string() == b.to_string() {
+		return Some(true);
+	}
+	close(approximate(a)?, approximate(b)?)
+}
+
+// compares floating point values at the point, skipping points where either is undefined
+fn compare_approximations(
+	a: &Expression,
+	b: &Expression,
+	point: &[(String, Expression)],
+) -> Option<bool> {
+	close(approximate_at(a, point)?, approximate_at(b, point)?)
+}
+
+fn close(x: f64, y: f64) -> Option<bool> {
+	if !x.is_finite() || !y.is_finite() {
+		return None;
+	}
+	Some((x - y).abs() <= 1e-9 * x.abs().max(1.0))
+}
+
+// the value at the point in floating point
+// None for trig functions, whose numeral arguments are in degrees
+fn approximate_at(exp: &Expression, point: &[(String, Expression)]) -> Option<f64> {
+	match exp {
+		Expression::Variable(v) if v == E => Some(std::f64::consts::E),
+		Expression::Variable(v) if v == "\\pi" => Some(std::f64::consts::PI),
+		Expression::Variable(v) => approximate(&point.iter().find(|(var, _)| var == v)?.1),
+		Expression::Numeral(_) => approximate(exp),
+		Expression::Sum(s) => s.terms.iter().map(|t| approximate_at(t, point)).sum(),
+		Expression::Product(p) => {
+			let coefficient = approximate(&Expression::Numeral(p.coefficient))?;
+			p.factors
+				.iter()
+				.map(|f| approximate_at(f, point))
+				.product::<Option<f64>>()
+				.map(|x| x * coefficient)
+		}
+		Expression::Quotient(q) => {
+			Some(approximate_at(&q.numerator, point)? / approximate_at(&q.denominator, point)?)
+		}
+		Expression::Exponent(e) => {
+			let base = approximate_at(&e.base, point)?;
+			match e.exponent.as_ref() {
+				// odd roots of negative numbers are real
+				Expression::Numeral(n) if base < 0.0 && n.denominator % 2 == 1 => {
+					let root = (-base).powf(n.numerator as f64 / n.denominator as f64);
+					Some(if n.numerator % 2 == 0 { root } else { -root })
+				}
+				exponent => Some(base.powf(approximate_at(exponent, point)?)),
+			}
+		}
+		Expression::Fn(Fn::Brackets(b)) => approximate_at(&b.expression, point),
+		Expression::Fn(Fn::Abs(a)) => Some(approximate_at(&a.argument, point)?.abs()),
+		Expression::Fn(Fn::Log(l)) => {
+			Some(approximate_at(&l.argument, point)?.ln() / approximate_at(&l.base, point)?.ln())
+		}
+		Expression::Fn(Fn::Trig(_)) => None,
+	}
+}
+
+fn is_expandable(exp: &Expression) -> bool {
+	is_exact(exp, &[]) && largest_power(exp) <= MAX_EXPANDED_POWER
+}
+
+fn largest_power(exp: &Expression) -> u32 {
+	match exp {
+		Expression::Sum(s) => s.terms.iter().map(|t| largest_power(t)).max().unwrap_or(0),
+		Expression::Product(p) => p
+			.factors
+			.iter()
+			.map(|f| largest_power(f))
+			.max()
+			.unwrap_or(0),
+		Expression::Quotient(q) => largest_power(&q.numerator).max(largest_power(&q.denominator)),
+		Expression::Exponent(e) => {
+			let power = match e.exponent.as_ref() {
+				Expression::Numeral(n) => n.numerator.unsigned_abs(),
+				_ => 0,
+			};
+			power.max(largest_power(&e.base))
+		}
+		_ => 0,
+	}
+}
+
+// true if exact working is small enough not to overflow
+// variables not in the point, as when expanding, count as 1
+fn is_exact(exp: &Expression, point: &[(String, Expression)]) -> bool {
+	size(exp, point) <= MAX_EXACT
+}
+
+// a bound on the numerators and denominators met while evaluating exactly
+fn size(exp: &Expression, point: &[(String, Expression)]) -> f64 {
+	let numeral = |n: &Fraction| (n.numerator.unsigned_abs() as f64).max(n.denominator as f64);
+	match exp {
+		Expression::Numeral(n) => numeral(n),
+		Expression::Variable(v) => match point.iter().find(|(var, _)| var == v) {
+			Some((_, Expression::Numeral(n))) => numeral(n),
+			_ => 1.0,
+		},
+		// \frac{a}{b} + \frac{c}{d} = \frac{ad + cb}{bd}
+		Expression::Sum(s) => s
+			.terms
+			.iter()
+			.fold(0.5, |bound, t| 2.0 * bound * size(t, point)),
+		Expression::Product(p) => p
+			.factors
+			.iter()
+			.fold(numeral(&p.coefficient), |bound, f| bound * size(f, point)),
+		Expression::Quotient(q) => size(&q.numerator, point) * size(&q.denominator, point),
+		Expression::Exponent(e) => {
+			let base = size(&e.base, point);
+			let power = match e.exponent.as_ref() {
+				Expression::Numeral(n) => n.numerator.unsigned_abs() as f64,
+				exponent => size(exponent, point),
+			};
+			base.powf(power).max(base)
+		}
+		Expression::Fn(Fn::Brackets(b)) => size(&b.expression, point),
+		Expression::Fn(Fn::Trig(t)) => size(&t.argument, point),
+		Expression::Fn(Fn::Log(l)) => size(&l.argument, point).max(size(&l.base, point)),
+		Expression::Fn(Fn::Abs(a)) => size(&a.argument, point),
+	}
+}
+
+// false if a denominator, or the base of a negative power, is zero at the point,
+// if a logarithm has an argument that is not positive,
+// or if an even root has a negative radicand
+// inner expressions are checked first as evaluating an outer one simplifies them
+fn is_defined(exp: &Expression, point: &[(String, Expression)]) -> bool {
+	let is_zero =
+		|exp: &Expression| matches!(evaluate(exp, point), Expression::Numeral(n) if n.is_zero());
+	match exp {
+		Expression::Sum(s) => s.terms.iter().all(|t| is_defined(t, point)),
+		Expression::Product(p) => p.factors.iter().all(|f| is_defined(f, point)),
+		Expression::Quotient(q) => {
+			is_defined(&q.numerator, point)
+				&& is_defined(&q.denominator, point)
+				&& !is_zero(&q.denominator)
+		}
+		Expression::Exponent(e) => {
+			let exponent = evaluate(&e.exponent, point);
+			let even_root = matches!(exponent, Expression::Numeral(n) if n.denominator % 2 == 0);
+			is_defined(&e.base, point)
+				&& is_defined(&e.exponent, point)
+				&& !(is_negative(&exponent) && is_zero(&e.base))
+				&& !(even_root && is_negative(&evaluate(&e.base, point)))
+		}
+		Expression::Fn(Fn::Log(l)) => {
+			is_defined(&l.argument, point)
+				&& is_defined(&l.base, point)
+				&& !(is_zero(&l.argument) || is_negative(&evaluate(&l.argument, point)))
+		}
+		Expression::Fn(Fn::Brackets(b)) => is_defined(&b.expression, point),
+		Expression::Fn(Fn::Trig(t)) => is_defined(&t.argument, point),
+		Expression::Fn(Fn::Abs(a)) => is_defined(&a.argument, point),
+		_ => true,
+	}
+}
+
+fn is_negative(exp: &Expression) -> bool {
+	match exp {
+		Expression::Numeral(n) => n.is_negative(),
+		_ => approximate(exp).is_some_and(|x| x < 0.0),
+	}
+}
@@ -0,0 +1,447 @@
+use crate::calculus::{polynomial_coefficients, polynomial_roots};
+use crate::expression::{fraction_gcd, Expression, Fraction, Quotient, Sum};
+use std::fmt;
+
+#[cfg(test)]
+mod tests {
+	use crate::expression::*;
+	use crate::marking::*;
+	use crate::*;
+
+	#[test]
+	fn expanded() {
+		assert!(sum!(exp!("x", 2), prod!(2, "x"), 1).is_expanded().is_ok());
+		let exp = sum!(prod!(2, sum!("x", 1)), "y");
+		assert_eq!(
+			exp.is_expanded().unwrap_err().to_string(),
+			"2\\left( x + 1 \\right) can be expanded"
+		);
+		let exp = sum!(exp!(sum!("x", 1), 2), 3);
+		assert_eq!(
+			exp.is_expanded().unwrap_err().to_string(),
+			"\\left( x + 1 \\right)^2 can be expanded"
+		);
+		assert!(quotient!(sum!("x", 1), prod!(2, "x")).is_expanded().is_ok());
+	}
+
+	#[test]
+	fn like_terms() {
+		let exp = sum_verbatim!(prod!(3, "x"), "y", prod!(-2, "x"));
+		assert_eq!(
+			exp.has_no_like_terms().unwrap_err().to_string(),
+			"3x and - 2x are like terms"
+		);
+		let exp = quotient!(sum_verbatim!(1, "x", 2), "y");
+		assert!(matches!(
+			exp.has_no_like_terms(),
+			Err(FormError::LikeTerms(_, _))
+		));
+		assert!(sum!(prod!(3, "x"), "y").has_no_like_terms().is_ok());
+	}
+
+	#[test]
+	fn factorised() {
+		let exp = prod!(2, sum!("x", 3), sum!("x", -1));
+		assert!(exp.is_fully_factorised().is_ok());
+		assert!(sum!(exp!("x", 2), 1).is_fully_factorised().is_ok());
+		let exp = prod!(sum!(prod!(2, "x"), 4), "y");
+		assert_eq!(
+			exp.is_fully_factorised().unwrap_err().to_string(),
+			"2x + 4 can be factorised further"
+		);
+		let exp = prod!(3, sum!(exp!("x", 2), prod!(-5, "x"), 6));
+		assert_eq!(
+			exp.is_fully_factorised().unwrap_err().to_string(),
+			"x^2 - 5x + 6 can be factorised further"
+		);
+		let exp = sum!(exp!("x", 2), prod!(-1, "x"));
+		assert_eq!(
+			exp.is_fully_factorised().unwrap_err().to_string(),
+			"x^2 - x can be factorised further"
+		);
+		let exp = sum!(exp!("x", 2), prod!(-1, exp!("y", 2)));
+		assert_eq!(
+			exp.is_fully_factorised().unwrap_err().to_string(),
+			"x^2 - y^2 can be factorised further"
+		);
+		let exp = sum!(exp!("x", 4), prod!(2, exp!("x", 2)), 1);
+		assert_eq!(
+			exp.is_fully_factorised().unwrap_err().to_string(),
+			"x^4 + 2x^2 + 1 can be factorised further"
+		);
+		let exp = sum!(exp!("x", 4), 4);
+		assert_eq!(
+			exp.is_fully_factorised().unwrap_err().to_string(),
+			"could not determine whether x^4 + 4 can be factorised further"
+		);
+		assert!(sum!(exp!("x", 2), exp!("y", 2))
+			.is_fully_factorised()
+			.is_ok());
+	}
+
+	#[test]
+	fn single_fraction() {
+		assert!(quotient!(sum!("x", 1), 6).is_single_fraction().is_ok());
+		assert!(sum!("x", 1).is_single_fraction().is_ok());
+		let exp = sum!(quotient!("x", 2), quotient!(1, 3));
+		assert_eq!(
+			exp.is_single_fraction().unwrap_err().to_string(),
+			"\\frac{x}{2} + \\frac{1}{3} is not a single fraction"
+		);
+		let exp = sum!(quotient!(1, "x"), 1);
+		assert_eq!(
+			exp.is_single_fraction().unwrap_err().to_string(),
+			"\\frac{1}{x} + 1 is not a single fraction"
+		);
+		let exp = quotient!(sum!(quotient!(1, "x"), 1), 2);
+		assert!(exp.is_single_fraction().is_err());
+		let exp = prod!(2, sum!(quotient!(1, "x"), 1));
+		assert!(exp.is_single_fraction().is_err());
+	}
+
+	#[test]
+	fn lowest_terms() {
+		assert!(quotient!(sum!("x", 1), sum!("x", 2))
+			.is_in_lowest_terms()
+			.is_ok());
+		let exp = Expression::Quotient(Quotient {
+			numerator: Box::new(sum!(prod!(2, "x"), 4)),
+			denominator: Box::new(6.into()),
+		});
+		assert_eq!(
+			exp.is_in_lowest_terms().unwrap_err().to_string(),
+			"the numerator and denominator have a common factor of 2"
+		);
+		let exp = Expression::Quotient(Quotient {
+			numerator: Box::new(sum!(exp!("x", 2), -1)),
+			denominator: Box::new(sum!("x", 1)),
+		});
+		assert_eq!(
+			exp.is_in_lowest_terms().unwrap_err().to_string(),
+			"the numerator and denominator have a common factor of x + 1"
+		);
+		let exp = Expression::Quotient(Quotient {
+			numerator: Box::new(prod!("x", "y")),
+			denominator: Box::new(prod!(2, "x")),
+		});
+		assert_eq!(
+			exp.is_in_lowest_terms().unwrap_err().to_string(),
+			"the numerator and denominator have a common factor of x"
+		);
+		// fractional coefficients have no numeral common factor
+		let exp = Expression::Quotient(Quotient {
+			numerator: Box::new(sum!(prod!(Fraction::new(1, 2), "x"), 1)),
+			denominator: Box::new(3.into()),
+		});
+		assert!(exp.is_in_lowest_terms().is_ok());
+	}
+}
+
+// why an expression is not in the required form
+#[derive(Debug, Clone)]
+pub enum FormError {
+	// a bracket that can be expanded
+	Brackets(Expression),
+	LikeTerms(Expression, Expression),
+	// a factor that can be factorised further
+	NotFactorised(Expression),
+	// a sum with fractional terms
+	SeparateFractions(Expression),
+	// a factor common to the numerator and denominator
+	CommonFactor(Expression),
+	// a factor that could not be checked, such as a quartic with no rational roots
+	Undetermined(Expression),
+}
+
+impl fmt::Display for FormError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			FormError::Brackets(exp) => write!(f, "{} can be expanded", exp),
+			FormError::LikeTerms(a, b) => write!(f, "{} and {} are like terms", a, b),
+			FormError::NotFactorised(exp) => write!(f, "{} can be factorised further", exp),
+			FormError::SeparateFractions(exp) => write!(f, "{} is not a single fraction", exp),
+			FormError::CommonFactor(exp) => {
+				write!(
+					f,
+					"the numerator and denominator have a common factor of {}",
+					exp
+				)
+			}
+			FormError::Undetermined(exp) => {
+				write!(
+					f,
+					"could not determine whether {} can be factorised further",
+					exp
+				)
+			}
+		}
+	}
+}
+
+impl Expression {
+	// no products or powers of sums remain, apart from in function arguments
+	// the numerator and denominator of a quotient are checked separately
+	pub fn is_expanded(&self) -> Result<(), FormError> {
+		match self {
+			Expression::Sum(s) => s.terms.iter().try_for_each(|t| t.is_expanded()),
+			Expression::Product(p) => {
+				let has_bracket = p.factors.iter().any(|f| is_bracket(f));
+				if has_bracket && (p.factors.len() > 1 || !p.coefficient.is_one()) {
+					return Err(FormError::Brackets(self.clone()));
+				}
+				p.factors.iter().try_for_each(|f| f.is_expanded())
+			}
+			Expression::Quotient(q) => {
+				q.numerator.is_expanded()?;
+				q.denominator.is_expanded()
+			}
+			Expression::Exponent(e) => match e.exponent.as_ref() {
+				Expression::Numeral(n) if is_bracket(&e.base) && n.is_integer() && n.numerator > 1 => {
+					Err(FormError::Brackets(self.clone()))
+				}
+				_ => e.base.is_expanded(),
+			},
+			_ => Ok(()),
+		}
+	}
+
+	// no sum in the expression has a pair of like terms
+	// reports the first pair found
+	pub fn has_no_like_terms(&self) -> Result<(), FormError> {
+		match self {
+			Expression::Sum(s) => {
+				if let Some((i, j)) = s.like_terms() {
+					return Err(FormError::LikeTerms(
+						*s.terms[i].clone(),
+						*s.terms[j].clone(),
+					));
+				}
+				s.terms.iter().try_for_each(|t| t.has_no_like_terms())
+			}
+			Expression::Product(p) => p.factors.iter().try_for_each(|f| f.has_no_like_terms()),
+			Expression::Quotient(q) => {
+				q.numerator.has_no_like_terms()?;
+				q.denominator.has_no_like_terms()
+			}
+			Expression::Exponent(e) => e.base.has_no_like_terms(),
+			_ => Ok(()),
+		}
+	}
+
+	// no sum factor has a common factor (checked with factorize), is a difference of two squares
+	// or, as a polynomial in one variable, has a rational root in x or x^2
+	// returns FormError::Undetermined for polynomials of degree 4 or more that pass these checks
+	pub fn is_fully_factorised(&self) -> Result<(), FormError> {
+		match self {
+			Expression::Sum(_) => is_irreducible(self),
+			Expression::Product(p) => p.factors.iter().try_for_each(|f| f.is_fully_factorised()),
+			Expression::Quotient(q) => {
+				q.numerator.is_fully_factorised()?;
+				q.denominator.is_fully_factorised()
+			}
+			Expression::Exponent(e) => e.base.is_fully_factorised(),
+			_ => Ok(()),
+		}
+	}
+
+	// a single quotient, or a sum with no fractional terms
+	// the numerator and denominator of a quotient have no fractions of their own
+	pub fn is_single_fraction(&self) -> Result<(), FormError> {
+		match self {
+			Expression::Sum(s) => {
+				if s.terms.iter().any(|t| has_fraction(t)) {
+					return Err(FormError::SeparateFractions(self.clone()));
+				}
+				s.terms.iter().try_for_each(|t| t.is_single_fraction())
+			}
+			Expression::Product(p) => p.factors.iter().try_for_each(|f| f.is_single_fraction()),
+			Expression::Quotient(q) => {
+				if has_fraction(&q.numerator) || has_fraction(&q.denominator) {
+					return Err(FormError::SeparateFractions(self.clone()));
+				}
+				q.numerator.is_single_fraction()?;
+				q.denominator.is_single_fraction()
+			}
+			Expression::Exponent(e) => e.base.is_single_fraction(),
+			_ => Ok(()),
+		}
+	}
+
+	// the numerator and denominator of every quotient have no common factor
+	// looks for factors cancelled by Quotient::simplify, common numeral factors of integer coefficients
+	// and common linear factors of polynomials in one variable
+	pub fn is_in_lowest_terms(&self) -> Result<(), FormError> {
+		match self {
+			Expression::Sum(s) => s.terms.iter().try_for_each(|t| t.is_in_lowest_terms()),
+			Expression::Product(p) => p.factors.iter().try_for_each(|f| f.is_in_lowest_terms()),
+			Expression::Exponent(e) => e.base.is_in_lowest_terms(),
+			Expression::Quotient(q) => {
+				let (a, b) = (content(&q.numerator), content(&q.denominator));
+				if a.is_integer() && b.is_integer() {
+					let k = fraction_gcd(&a, &b);
+					if k.numerator > 1 {
+						return Err(FormError::CommonFactor(Expression::Numeral(k)));
+					}
+				}
+				if let Some(factor) = common_linear_factor(&q.numerator, &q.denominator) {
+					return Err(FormError::CommonFactor(factor));
+				}
+				let mut simplified = q.clone();
+				simplified.simplify();
+				if simplified.to_string() != q.to_string() {
+					// the factor that was cancelled
+					let mut factor = Expression::Quotient(Quotient {
+						numerator: q.numerator.clone(),
+						denominator: simplified.numerator.clone(),
+					});
+					factor.simplify();
+					return Err(FormError::CommonFactor(factor));
+				}
+				Ok(())
+			}
+			_ => Ok(()),
+		}
+	}
+}
+
+fn is_bracket(exp: &Expression) -> bool {
+	matches!(exp, Expression::Sum(s) if s.terms.len() > 1)
+}
+
+fn has_fraction(exp: &Expression) -> bool {
+	match exp {
+		Expression::Quotient(_) => true,
+		Expression::Numeral(n) => !n.is_integer(),
+		Expression::Product(p) => {
+			!p.coefficient.is_integer() || p.factors.iter().any(|f| has_fraction(f))
+		}
+		_ => false,
+	}
+}
+
+// a sum with no common factor, that is not a difference of two squares
+// and, as a polynomial in one variable, has no rational roots in x or x^2
+// polynomials of degree 4 or more are undetermined as they may split into quadratics
+fn is_irreducible(exp: &Expression) -> Result<(), FormError> {
+	let mut factorised = exp.clone();
+	factorised.factorize();
+	if factorised.to_string() != exp.to_string() || is_difference_of_squares(exp) {
+		return Err(FormError::NotFactorised(exp.clone()));
+	}
+	if let [var] = exp.variables().as_slice() {
+		if let Some(coefficients) = polynomial_coefficients(exp, var) {
+			if coefficients.len() > 2 && !rational_roots(&coefficients).is_empty() {
+				return Err(FormError::NotFactorised(exp.clone()));
+			}
+			// x^4 + 2x^2 + 1 as a quadratic in x^2
+			let odd_powers = coefficients.iter().skip(1).step_by(2);
+			if coefficients.len() > 3 && odd_powers.clone().all(|c| c.is_zero()) {
+				let even_powers: Vec<Fraction> = coefficients.iter().step_by(2).copied().collect();
+				if !rational_roots(&even_powers).is_empty() {
+					return Err(FormError::NotFactorised(exp.clone()));
+				}
+			}
+			if coefficients.len() > 4 {
+				return Err(FormError::Undetermined(exp.clone()));
+			}
+		}
+	}
+	Ok(())
+}
+
+// a^2 - b^2 where a and b are monomials
+fn is_difference_of_squares(exp: &Expression) -> bool {
+	match exp {
+		Expression::Sum(s) => match s.terms.as_slice() {
+			[a, b] => {
+				coefficient(a).is_negative() != coefficient(b).is_negative() && is_square(a) && is_square(b)
+			}
+			_ => false,
+		},
+		_ => false,
+	}
+}
+
+fn coefficient(term: &Expression) -> Fraction {
+	match term {
+		Expression::Numeral(n) => *n,
+		Expression::Product(p) => p.coefficient,
+		_ => 1.into(),
+	}
+}
+
+// a monomial with a square coefficient, up to sign, and even powers
+fn is_square(term: &Expression) -> bool {
+	let is_square_integer = |n: i64| {
+		let root = (n as f64).sqrt().round() as i64;
+		root * root == n
+	};
+	match term {
+		Expression::Numeral(n) => {
+			is_square_integer(n.numerator.unsigned_abs() as i64)
+				&& is_square_integer(n.denominator as i64)
+		}
+		Expression::Product(p) => {
+			is_square(&Expression::Numeral(p.coefficient)) && p.factors.iter().all(|f| is_square(f))
+		}
+		Expression::Exponent(e) => {
+			matches!(e.base.as_ref(), Expression::Variable(_))
+				&& matches!(e.exponent.as_ref(), Expression::Numeral(n) if n.is_integer() && n.numerator % 2 == 0)
+		}
+		_ => false,
+	}
+}
+
+fn rational_roots(coefficients: &[Fraction]) -> Vec<Fraction> {
+	polynomial_roots(coefficients)
+		.unwrap_or_default()
+		.into_iter()
+		.filter_map(|(r, _)| match r {
+			Expression::Numeral(n) => Some(n),
+			_ => None,
+		})
+		.collect()
+}
+
+// the highest common factor of the numeral coefficients
+fn content(exp: &Expression) -> Fraction {
+	let mut exp = exp.clone();
+	exp.expand_and_simplify();
+	let coefficient = |t: &Expression| match t {
+		Expression::Numeral(n) => n.abs(),
+		Expression::Product(p) => p.coefficient.abs(),
+		_ => 1.into(),
+	};
+	match &exp {
+		Expression::Sum(s) => s
+			.terms
+			.iter()
+			.map(|t| coefficient(t))
+			.reduce(|a, b| fraction_gcd(&a, &b))
+			.unwrap_or(1.into()),
+		_ => coefficient(&exp),
+	}
+}
+
+// (x - r) for a rational root r shared by the numerator and denominator
+fn common_linear_factor(numerator: &Expression, denominator: &Expression) -> Option<Expression> {
+	let mut variables = numerator.variables();
+	variables.extend(denominator.variables());
+	variables.sort();
+	variables.dedup();
+	let var = match variables.as_slice() {
+		[var] => var,
+		_ => return None,
+	};
+	let a = rational_roots(&polynomial_coefficients(numerator, var)?);
+	let b = rational_roots(&polynomial_coefficients(denominator, var)?);
+	let r = a.into_iter().find(|r| b.contains(r))?;
+	let mut factor = Expression::Sum(Sum {
+		terms: vec![
+			Box::new(var.as_str().into()),
+			Box::new(Expression::Numeral(r.negative())),
+		],
+	});
+	factor.simplify();
+	Some(factor)
+}
@@ -0,0 +1,6 @@
+pub mod equivalence;
+pub mod form;
+pub mod working;
+pub use equivalence::{check_equivalence, Equivalence};
+pub use form::FormError;
+pub use working::{mark_working, ErrorKind, Marking, StepError};
@@ -0,0 +1,246 @@
+use crate::expression::{like_term_key, Expression, Fraction, Product, Sum};
+use crate::marking::{check_equivalence, Equivalence};
+use std::fmt;
+
+#[cfg(test)]
+mod tests {
+	use crate::expression::*;
+	use crate::marking::*;
+	use crate::*;
+
+	// 2(x + 3) - (x - 4)
+	fn question() -> Expression {
+		sum_verbatim!(prod!(2, sum!("x", 3)), prod!(-1, sum!("x", -4)))
+	}
+
+	#[test]
+	fn correct_working() {
+		let working = vec![
+			sum_verbatim!(prod!(2, "x"), 6, prod!(-1, "x"), 4),
+			sum!("x", 10),
+		];
+		let marking = mark_working(&question(), &working);
+		assert_eq!(marking.valid_lines, 2);
+		assert!(marking.first_error.is_none());
+		assert_eq!(marking.credit, 1.into());
+	}
+
+	#[test]
+	fn sign_error() {
+		let working = vec![
+			sum_verbatim!(prod!(2, "x"), 6, prod!(-1, "x"), -4),
+			sum!("x", 2),
+		];
+		let marking = mark_working(&question(), &working);
+		assert_eq!(marking.valid_lines, 0);
+		assert_eq!(marking.credit, 0.into());
+		assert_eq!(
+			marking.first_error.unwrap().to_string(),
+			"line 1: sign error in - 4"
+		);
+		// the error is only found on the second line
+		let working = vec![
+			sum_verbatim!(prod!(2, "x"), 6, prod!(-1, "x"), 4),
+			sum!("x", 2),
+		];
+		let marking = mark_working(&question(), &working);
+		assert_eq!(marking.valid_lines, 1);
+		assert_eq!(marking.credit, Fraction::new(1, 2));
+		assert_eq!(
+			marking.first_error.unwrap().to_string(),
+			"line 2: the constant term should be 10"
+		);
+	}
+
+	#[test]
+	fn dropped_term_and_coefficient() {
+		let question = exp!(sum!("x", 2), 2);
+		let marking = mark_working(&question, &[sum!(exp!("x", 2), 4)]);
+		let error = marking.first_error.unwrap();
+		assert!(matches!(error.kind, ErrorKind::DroppedTerm(_)));
+		assert_eq!(error.to_string(), "line 1: the term 4x is missing");
+		let marking = mark_working(&question, &[sum!(exp!("x", 2), prod!(2, "x"), 4)]);
+		assert_eq!(
+			marking.first_error.unwrap().to_string(),
+			"line 1: the coefficient of 2x should be 4"
+		);
+		let marking = mark_working(&question, &[sum!(exp!("x", 3), 4)]);
+		let error = marking.first_error.unwrap();
+		assert!(matches!(error.kind, ErrorKind::Unclassified));
+		assert_eq!(
+			error.to_string(),
+			"line 1: x^3 + 4 does not follow from \\left( x + 2 \\right)^2"
+		);
+	}
+
+	#[test]
+	fn extra_term_and_no_working() {
+		let question = exp!(sum!("x", 2), 2);
+		let working = [sum!(exp!("x", 3), exp!("x", 2), prod!(4, "x"), 4)];
+		let marking = mark_working(&question, &working);
+		let error = marking.first_error.unwrap();
+		assert!(matches!(error.kind, ErrorKind::ExtraTerm(_)));
+		assert_eq!(
+			error.to_string(),
+			"line 1: the term x^3 should not be there"
+		);
+		let marking = mark_working(&question, &[]);
+		assert_eq!(marking.valid_lines, 0);
+		assert!(marking.first_error.is_none());
+		assert_eq!(marking.credit, 0.into());
+	}
+}
+
+// the kind of mistake made on a line of working
+#[derive(Debug, Clone)]
+pub enum ErrorKind {
+	// the term with the wrong sign
+	SignError(Expression),
+	// the missing term
+	DroppedTerm(Expression),
+	// the term with the wrong coefficient, and the coefficient it should have
+	WrongCoefficient(Expression, Fraction),
+	// the term that should not be there
+	ExtraTerm(Expression),
+	Unclassified,
+}
+
+// the first line of working that does not follow from the line before
+// lines are numbered from 1, with the question as line 0
+#[derive(Debug, Clone)]
+pub struct StepError {
+	pub line: usize,
+	pub kind: ErrorKind,
+	pub previous: Expression,
+	pub found: Expression,
+}
+
+impl fmt::Display for StepError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "line {}: ", self.line)?;
+		match &self.kind {
+			ErrorKind::SignError(term) => write!(f, "sign error in {}", term),
+			ErrorKind::DroppedTerm(term) => write!(f, "the term {} is missing", term),
+			ErrorKind::ExtraTerm(term) => write!(f, "the term {} should not be there", term),
+			ErrorKind::WrongCoefficient(Expression::Numeral(_), constant) => {
+				write!(f, "the constant term should be {}", constant)
+			}
+			ErrorKind::WrongCoefficient(term, coefficient) => {
+				write!(f, "the coefficient of {} should be {}", term, coefficient)
+			}
+			ErrorKind::Unclassified => write!(f, "{} does not follow from {}", self.found, self.previous),
+		}
+	}
+}
+
+#[derive(Debug, Clone)]
+pub struct Marking {
+	// lines before the first error
+	pub valid_lines: usize,
+	pub first_error: Option<StepError>,
+	// the proportion of lines before the first error, or 0 for no working
+	pub credit: Fraction,
+}
+
+// checks that each line is equivalent to the one before, starting from the question
+// lines whose equivalence cannot be determined are given the benefit of the doubt
+pub fn mark_working(question: &Expression, working: &[Expression]) -> Marking {
+	if working.is_empty() {
+		return Marking {
+			valid_lines: 0,
+			first_error: None,
+			credit: 0.into(),
+		};
+	}
+	let mut previous = question;
+	for (i, line) in working.iter().enumerate() {
+		if check_equivalence(previous, line) == Equivalence::NotEquivalent {
+			return Marking {
+				valid_lines: i,
+				first_error: Some(StepError {
+					line: i + 1,
+					kind: classify(previous, line),
+					previous: previous.clone(),
+					found: line.clone(),
+				}),
+				credit: Fraction::new(i as i32, working.len() as i32),
+			};
+		}
+		previous = line;
+	}
+	Marking {
+		valid_lines: working.len(),
+		first_error: None,
+		credit: 1.into(),
+	}
+}
+
+fn terms(exp: &Expression) -> Vec<Expression> {
+	match exp {
+		Expression::Sum(s) => s.terms.iter().map(|t| t.as_ref().clone()).collect(),
+		_ => vec![exp.clone()],
+	}
+}
+
+fn negative(exp: &Expression) -> Expression {
+	let mut exp = Expression::Product(Product {
+		coefficient: (-1).into(),
+		factors: vec![Box::new(exp.clone())],
+	});
+	exp.simplify();
+	exp
+}
+
+// a sign error if negating one term of the line makes it correct
+// otherwise compares the missing part, previous - line, with the terms of the line
+fn classify(previous: &Expression, line: &Expression) -> ErrorKind {
+	let written = terms(line);
+	for i in 0..written.len() {
+		let corrected = Expression::Sum(Sum {
+			terms: written
+				.iter()
+				.enumerate()
+				.map(|(j, t)| Box::new(if i == j { negative(t) } else { t.clone() }))
+				.collect(),
+		});
+		if check_equivalence(previous, &corrected) == Equivalence::Equivalent {
+			return ErrorKind::SignError(written[i].clone());
+		}
+	}
+	let mut difference = Expression::Sum(Sum {
+		terms: vec![Box::new(previous.clone()), Box::new(negative(line))],
+	});
+	difference.expand_and_simplify();
+	if matches!(difference, Expression::Sum(_)) {
+		return ErrorKind::Unclassified;
+	}
+	let mut simplified = line.clone();
+	simplified.expand_and_simplify();
+	let key = like_term_key(&difference);
+	match terms(&simplified)
+		.into_iter()
+		.find(|t| like_term_key(t) == key)
+	{
+		Some(term) => {
+			let mut expected = Expression::Sum(Sum {
+				terms: vec![Box::new(term.clone()), Box::new(difference)],
+			});
+			expected.simplify();
+			let coefficient = coefficient(&expected);
+			if coefficient.is_zero() {
+				ErrorKind::ExtraTerm(term)
+			} else {
+				ErrorKind::WrongCoefficient(term, coefficient)
+			}
+		}
+		None => ErrorKind::DroppedTerm(difference),
+	}
+}
+
+fn coefficient(term: &Expression) -> Fraction {
+	match term {
+		Expression::Product(p) => p.coefficient,
+		Expression::Numeral(n) => *n,
+		_ => 1.into(),
+	}
+}
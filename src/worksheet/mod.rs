@@ -0,0 +1,305 @@
+use crate::generate::Question;
+use std::fmt;
+
+#[cfg(test)]
+mod tests {
+	use crate::expression::*;
+	use crate::generate::*;
+	use crate::worksheet::*;
+	use crate::*;
+
+	#[test]
+	fn single_parts() {
+		let mut sheet = Worksheet::new("Quadratics");
+		sheet.add_question(
+			"Factorise",
+			vec![Part::new(
+				sum!(exp!("x", 2), prod!(-1, 4)),
+				prod!(sum!("x", 2), sum!("x", -2)),
+			)],
+		);
+		let latex = sheet.to_string();
+		let lines: Vec<&str> = latex.lines().collect();
+		assert_eq!(
+			lines,
+			vec![
+				"\\documentclass{article}",
+				"\\usepackage{amsmath}",
+				"\\usepackage{enumitem}",
+				"\\usepackage{multicol}",
+				"\\begin{document}",
+				"\\section*{Quadratics}",
+				"\\begin{enumerate}",
+				"\\item Factorise",
+				"$x^2 - 4$",
+				"\\vspace{2em}",
+				"\\end{enumerate}",
+				"\\newpage",
+				"\\section*{Answers}",
+				"\\begin{enumerate}",
+				"\\item Factorise",
+				"$\\left( x + 2 \\right)\\left( x - 2 \\right)$",
+				"\\end{enumerate}",
+				"\\end{document}"
+			]
+		);
+	}
+
+	#[test]
+	fn parts_and_solutions() {
+		let mut sheet = Worksheet::new("Algebra");
+		sheet.columns = 2;
+		sheet.spacing = "1cm".to_string();
+		sheet.preamble = "\\usepackage{geometry}".to_string();
+		let questions = generate_many(&Topic::Expand, &Constraints::default(), 1, 2).unwrap();
+		sheet.add_generated(&questions);
+		sheet.add_question(
+			"Evaluate",
+			vec![
+				Part::new("\\int_0^1 x \\, \\mathrm{d}x", Fraction::new(1, 2)).with_solution(vec![
+					"\\int_0^1 x \\, \\mathrm{d}x = \\left[ \\frac{1}{2}x^2 \\right]_0^1".to_string(),
+					"= \\frac{1}{2}".to_string(),
+				]),
+			],
+		);
+		let latex = sheet.to_string();
+		let lines: Vec<&str> = latex.lines().collect();
+		assert_eq!(
+			lines,
+			vec![
+				"\\documentclass{article}",
+				"\\usepackage{amsmath}",
+				"\\usepackage{enumitem}",
+				"\\usepackage{multicol}",
+				"\\usepackage{geometry}",
+				"\\begin{document}",
+				"\\section*{Algebra}",
+				"\\begin{enumerate}",
+				"\\item Expand and simplify",
+				"\\begin{multicols}{2}",
+				"\\begin{enumerate}[label=(\\alph*)]",
+				"\\item $\\left( - x + 5 \\right)\\left( 4x + 5 \\right)$",
+				"\\vspace{1cm}",
+				"\\item $\\left( 4x - 7 \\right)\\left( - 9x - 1 \\right)$",
+				"\\vspace{1cm}",
+				"\\end{enumerate}",
+				"\\end{multicols}",
+				"\\item Evaluate",
+				"$\\int_0^1 x \\, \\mathrm{d}x$",
+				"\\vspace{1cm}",
+				"\\end{enumerate}",
+				"\\newpage",
+				"\\section*{Answers}",
+				"\\begin{enumerate}",
+				"\\item Expand and simplify",
+				"\\begin{enumerate}[label=(\\alph*)]",
+				"\\item $- 4x^2 + 15x + 25$",
+				"\\item $- 36x^2 + 59x + 7$",
+				"\\end{enumerate}",
+				"\\item Evaluate",
+				"$\\frac{1}{2}$",
+				"\\begin{align*}",
+				"\\int_0^1 x \\, \\mathrm{d}x &= \\left[ \\frac{1}{2}x^2 \\right]_0^1 \\\\",
+				"&= \\frac{1}{2}",
+				"\\end{align*}",
+				"\\end{enumerate}",
+				"\\end{document}"
+			]
+		);
+	}
+
+	#[test]
+	fn empty_and_escaped() {
+		let sheet = Worksheet::new("Q&A: 50% of $x_1$ {#1} ~ ^ \\");
+		let latex = sheet.to_string();
+		let lines: Vec<&str> = latex.lines().collect();
+		assert_eq!(
+			lines,
+			vec![
+				"\\documentclass{article}",
+				"\\usepackage{amsmath}",
+				"\\usepackage{enumitem}",
+				"\\usepackage{multicol}",
+				"\\begin{document}",
+				"\\section*{Q\\&A: 50\\% of \\$x\\_1\\$ \\{\\#1\\} \\textasciitilde{} \\textasciicircum{} \\textbackslash{}}",
+				"\\newpage",
+				"\\section*{Answers}",
+				"\\end{document}"
+			]
+		);
+	}
+}
+
+// a question part with its answer and optional lines of working
+#[derive(Debug, Clone)]
+pub struct Part {
+	pub question: String,
+	pub answer: String,
+	pub solution: Vec<String>,
+}
+
+impl Part {
+	pub fn new<Q: fmt::Display, A: fmt::Display>(question: Q, answer: A) -> Part {
+		Part {
+			question: question.to_string(),
+			answer: answer.to_string(),
+			solution: Vec::new(),
+		}
+	}
+
+	pub fn with_solution(mut self, solution: Vec<String>) -> Part {
+		self.solution = solution;
+		self
+	}
+}
+
+// a numbered question: an instruction followed by parts (a), (b), (c), ...
+#[derive(Debug, Clone)]
+pub struct Item {
+	pub instruction: String,
+	pub parts: Vec<Part>,
+}
+
+// a LaTeX document with a questions section and an answer key
+// preamble is added after the default packages
+// columns and spacing apply to the parts of each question
+#[derive(Debug, Clone)]
+pub struct Worksheet {
+	pub title: String,
+	pub preamble: String,
+	pub columns: u32,
+	pub spacing: String,
+	pub items: Vec<Item>,
+}
+
+impl Worksheet {
+	pub fn new(title: &str) -> Worksheet {
+		Worksheet {
+			title: title.to_string(),
+			preamble: String::new(),
+			columns: 1,
+			spacing: "2em".to_string(),
+			items: Vec::new(),
+		}
+	}
+
+	pub fn add_question(&mut self, instruction: &str, parts: Vec<Part>) {
+		self.items.push(Item {
+			instruction: instruction.to_string(),
+			parts,
+		});
+	}
+
+	// one question per topic, in the order the topics first appear
+	pub fn add_generated(&mut self, questions: &[Question]) {
+		let mut topics = Vec::new();
+		for q in questions.iter() {
+			if !topics.contains(&q.topic) {
+				topics.push(q.topic);
+			}
+		}
+		for topic in topics {
+			let parts = questions
+				.iter()
+				.filter(|q| q.topic == topic)
+				.map(|q| Part::new(&q.question, &q.answer))
+				.collect();
+			self.add_question(&topic.to_string(), parts);
+		}
+	}
+
+	fn section(&self, lines: &mut Vec<String>, answers: bool) {
+		// an empty enumerate environment does not compile
+		if self.items.is_empty() {
+			return;
+		}
+		lines.push("\\begin{enumerate}".to_string());
+		for item in self.items.iter() {
+			lines.push(format!("\\item {}", item.instruction));
+			let columns = !answers && self.columns > 1 && item.parts.len() > 1;
+			if columns {
+				lines.push(format!("\\begin{{multicols}}{{{}}}", self.columns));
+			}
+			let labelled = item.parts.len() > 1;
+			if labelled {
+				lines.push("\\begin{enumerate}[label=(\\alph*)]".to_string());
+			}
+			for part in item.parts.iter() {
+				let text = if answers {
+					&part.answer
+				} else {
+					&part.question
+				};
+				let prefix = if labelled { "\\item " } else { "" };
+				lines.push(format!("{}${}$", prefix, text));
+				if answers && !part.solution.is_empty() {
+					lines.push("\\begin{align*}".to_string());
+					lines.push(align(&part.solution));
+					lines.push("\\end{align*}".to_string());
+				}
+				if !answers {
+					lines.push(format!("\\vspace{{{}}}", self.spacing));
+				}
+			}
+			if labelled {
+				lines.push("\\end{enumerate}".to_string());
+			}
+			if columns {
+				lines.push("\\end{multicols}".to_string());
+			}
+		}
+		lines.push("\\end{enumerate}".to_string());
+	}
+}
+
+// lines of working aligned at their first equals sign
+fn align(solution: &[String]) -> String {
+	solution
+		.iter()
+		.map(|line| match line.strip_prefix("= ") {
+			Some(rest) => format!("&= {}", rest),
+			None => line.replacen(" = ", " &= ", 1),
+		})
+		.collect::<Vec<String>>()
+		.join(" \\\\\n")
+}
+
+// the title as LaTeX text, with special characters escaped
+fn escape(text: &str) -> String {
+	let mut escaped = String::new();
+	for c in text.chars() {
+		match c {
+			'&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+				escaped.push('\\');
+				escaped.push(c);
+			}
+			'~' => escaped.push_str("\\textasciitilde{}"),
+			'^' => escaped.push_str("\\textasciicircum{}"),
+			'\\' => escaped.push_str("\\textbackslash{}"),
+			_ => escaped.push(c),
+		}
+	}
+	escaped
+}
+
+impl fmt::Display for Worksheet {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let mut lines: Vec<String> = vec![
+			"\\documentclass{article}".to_string(),
+			"\\usepackage{amsmath}".to_string(),
+			"\\usepackage{enumitem}".to_string(),
+			"\\usepackage{multicol}".to_string(),
+		];
+		if !self.preamble.is_empty() {
+			lines.push(self.preamble.clone());
+		}
+		lines.push("\\begin{document}".to_string());
+		lines.push(format!("\\section*{{{}}}", escape(&self.title)));
+		self.section(&mut lines, false);
+		lines.push("\\newpage".to_string());
+		lines.push("\\section*{Answers}".to_string());
+		self.section(&mut lines, true);
+		lines.push("\\end{document}".to_string());
+		write!(f, "{}", lines.join("\n"))
+	}
+}
@@ -29,25 +29,19 @@ fn factorize() {
 	let mut exp = sum!("x", exp!("x", 2), exp!("x", Fraction::new(3, 2)));
 	assert_eq!(exp.to_string(), "x + x^2 + x^{\\frac{3}{2}}");
 	exp.factorize();
-	assert_eq!(
-		exp.to_string(),
-		"x\\left( 1 + x + x^{\\frac{1}{2}} \\right)"
-	);
+	assert_eq!(exp.to_string(), "x\\left( 1 + x + \\sqrt{x} \\right)");
 	let mut exp = sum!("x", exp!("x", 2), exp!("x", Fraction::new(-1, 2)));
 	exp.factorize();
 	assert_eq!(exp.to_string(), "x + x^2 + x^{- \\frac{1}{2}}");
 	let mut exp = sum!(exp!("x", 2), "x", exp!("x", Fraction::new(3, 2)));
 	exp.factorize();
-	assert_eq!(
-		exp.to_string(),
-		"x\\left( x + 1 + x^{\\frac{1}{2}} \\right)"
-	);
+	assert_eq!(exp.to_string(), "x\\left( x + 1 + \\sqrt{x} \\right)");
 
 	let mut exp = sum!(exp!("x", 2), "x", exp!("x", Fraction::new(1, 2)));
 	exp.factorize();
 	assert_eq!(
 		exp.to_string(),
-		"x^{\\frac{1}{2}}\\left( x^{\\frac{3}{2}} + x^{\\frac{1}{2}} + 1 \\right)"
+		"\\sqrt{x}\\left( x^{\\frac{3}{2}} + \\sqrt{x} + 1 \\right)"
 	);
 
 	let mut exp = sum!(
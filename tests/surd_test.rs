@@ -0,0 +1,43 @@
+use mathlify::expression::*;
+use mathlify::*;
+
+#[test]
+fn rationalise_monomial() {
+	let mut exp = quotient!(1, sqrt!(2));
+	exp.rationalise_denominator();
+	assert_eq!(exp.to_string(), "\\frac{\\sqrt{2}}{2}");
+	let mut exp = quotient!(6, prod!(2, sqrt!(3)));
+	exp.rationalise_denominator();
+	assert_eq!(exp.to_string(), "\\sqrt{3}");
+	let mut exp = quotient!(sum!(1, sqrt!(2)), sqrt!(5));
+	exp.rationalise_denominator();
+	assert_eq!(exp.to_string(), "\\frac{\\sqrt{5} + \\sqrt{10}}{5}");
+	let mut exp = quotient!(1, exp!(2, Fraction::new(1, 3)));
+	exp.rationalise_denominator();
+	assert_eq!(exp.to_string(), "\\frac{\\sqrt[3]{4}}{2}");
+}
+
+#[test]
+fn rationalise_binomial() {
+	let mut exp = quotient!(3, sum!(2, prod!(-1, sqrt!(3))));
+	assert_eq!(exp.to_string(), "\\frac{3}{2 - \\sqrt{3}}");
+	exp.rationalise_denominator();
+	assert_eq!(exp.to_string(), "6 + 3\\sqrt{3}");
+	let mut exp = quotient!(1, sum!(sqrt!(5), sqrt!(2)));
+	exp.rationalise_denominator();
+	assert_eq!(exp.to_string(), "\\frac{\\sqrt{5} - \\sqrt{2}}{3}");
+	let mut exp = quotient!(sum!(1, sqrt!(2)), sum!(1, prod!(-1, sqrt!(2))));
+	exp.rationalise_denominator();
+	assert_eq!(exp.to_string(), "- 3 - 2\\sqrt{2}");
+	let mut exp = quotient!(4, sum!(3, sqrt!(5)));
+	exp.rationalise_denominator();
+	assert_eq!(exp.to_string(), "3 - \\sqrt{5}");
+	// the conjugate does not rationalise a cube root
+	let mut exp = quotient!(1, sum!(1, exp!(2, Fraction::new(1, 3))));
+	exp.rationalise_denominator();
+	assert_eq!(exp.to_string(), "\\frac{1}{1 + \\sqrt[3]{2}}");
+	// nothing to rationalise
+	let mut exp = quotient!(1, sum!("x", 1));
+	exp.rationalise_denominator();
+	assert_eq!(exp.to_string(), "\\frac{1}{x + 1}");
+}